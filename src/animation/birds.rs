@@ -1,5 +1,5 @@
 use crate::render::TerminalRenderer;
-use crossterm::style::Color;
+use crate::theme::Palette;
 use rand::prelude::*;
 use std::io;
 
@@ -16,6 +16,7 @@ pub struct BirdSystem {
     birds: Vec<Bird>,
     terminal_width: u16,
     terminal_height: u16,
+    spawning: bool,
 }
 
 impl BirdSystem {
@@ -24,9 +25,19 @@ impl BirdSystem {
             birds: Vec::with_capacity(3),
             terminal_width,
             terminal_height,
+            spawning: true,
         }
     }
 
+    /// Stops new birds from appearing; those in flight keep going until off screen.
+    pub fn set_spawning(&mut self, spawning: bool) {
+        self.spawning = spawning;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.spawning || !self.birds.is_empty()
+    }
+
     pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
@@ -42,7 +53,7 @@ impl BirdSystem {
         }
 
         self.birds.retain(|b| b.x < terminal_width as f32);
-        if self.birds.len() < 3 && rng.random::<f32>() < 0.01 {
+        if self.spawning && self.birds.len() < 3 && rng.random::<f32>() < 0.01 {
             let y = (rng.random::<u16>() % (terminal_height / 3)) as f32;
             let speed = 0.2 + (rng.random::<f32>() * 0.2);
             self.birds.push(Bird {
@@ -56,12 +67,12 @@ impl BirdSystem {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         for bird in &self.birds {
             let x = bird.x as u16;
             let y = bird.y as u16;
             if x < self.terminal_width && y < self.terminal_height {
-                renderer.render_char(x, y, bird.character, Color::Yellow)?;
+                renderer.render_char(x, y, bird.character, palette.sky.birds)?;
             }
         }
         Ok(())
@@ -1,4 +1,5 @@
 use crate::render::TerminalRenderer;
+use crate::theme::Palette;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
@@ -37,14 +38,14 @@ impl SmokeParticle {
         self.age < self.max_age
     }
 
-    fn get_color(&self) -> Color {
+    fn get_color(&self, palette: &Palette) -> Color {
         let life_ratio = self.age as f32 / self.max_age as f32;
         if life_ratio < 0.3 {
-            Color::White
+            palette.house.smoke
         } else if life_ratio < 0.6 {
-            Color::Grey
+            palette.house.smoke_fading
         } else {
-            Color::DarkGrey
+            palette.house.smoke_faint
         }
     }
 }
@@ -53,6 +54,7 @@ pub struct ChimneySmoke {
     particles: Vec<SmokeParticle>,
     spawn_counter: u32,
     spawn_rate: u32,
+    spawning: bool,
 }
 
 impl ChimneySmoke {
@@ -61,9 +63,19 @@ impl ChimneySmoke {
             particles: Vec::with_capacity(MAX_PARTICLES),
             spawn_counter: 0,
             spawn_rate: 8,
+            spawning: true,
         }
     }
 
+    /// Lights or puts out the fire; smoke already rising drifts away.
+    pub fn set_spawning(&mut self, spawning: bool) {
+        self.spawning = spawning;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.spawning || !self.particles.is_empty()
+    }
+
     pub fn update(&mut self, chimney_x: u16, chimney_y: u16, rng: &mut impl Rng) {
         for particle in &mut self.particles {
             particle.update();
@@ -72,14 +84,17 @@ impl ChimneySmoke {
         self.particles.retain(|p| p.is_alive() && p.y >= 0.0);
 
         self.spawn_counter += 1;
-        if self.spawn_counter >= self.spawn_rate && self.particles.len() < MAX_PARTICLES {
+        if self.spawning
+            && self.spawn_counter >= self.spawn_rate
+            && self.particles.len() < MAX_PARTICLES
+        {
             self.spawn_counter = 0;
             self.particles
                 .push(SmokeParticle::new(chimney_x, chimney_y, rng));
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         for particle in &self.particles {
             let x = particle.x as i16;
             let y = particle.y as i16;
@@ -92,7 +107,12 @@ impl ChimneySmoke {
                     _ => '·',
                 };
 
-                renderer.render_char(x as u16, y as u16, display_char, particle.get_color())?;
+                renderer.render_char(
+                    x as u16,
+                    y as u16,
+                    display_char,
+                    particle.get_color(palette),
+                )?;
             }
         }
         Ok(())
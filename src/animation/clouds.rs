@@ -1,4 +1,6 @@
+use super::transition::{FADE_DURATION, Fade};
 use crate::render::TerminalRenderer;
+use crate::theme::Palette;
 use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
@@ -6,89 +8,127 @@ use std::sync::OnceLock;
 
 static CLOUD_SHAPES: OnceLock<Vec<Vec<String>>> = OnceLock::new();
 
+/// Cloud cover assumed until weather data arrives.
+const DEFAULT_COVER: f32 = 0.3;
+/// At or below this cover the sky is drawn clear.
+const CLEAR_SKY_COVER: f32 = 0.05;
+
+static LARGE_CLOUD_SHAPES: OnceLock<Vec<Vec<String>>> = OnceLock::new();
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Layer {
+    Back,
+    Front,
+}
+
 struct Cloud {
     x: f32,
     y: f32,
     speed: f32,
     shape: Vec<String>,
-    color: Color,
+    layer: Layer,
 }
 
 pub struct CloudSystem {
     clouds: Vec<Cloud>,
     terminal_width: u16,
     terminal_height: u16,
+    fade: Fade,
+    cover: f32,
 }
 
 impl CloudSystem {
-    pub fn set_cloud_color(&mut self, is_clear: bool) {
-        let color = if is_clear {
-            Color::White
-        } else {
-            Color::DarkGrey
+    pub fn new(terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) -> Self {
+        let mut system = Self {
+            clouds: Vec::new(),
+            terminal_width,
+            terminal_height,
+            fade: Fade::full(FADE_DURATION),
+            cover: DEFAULT_COVER,
         };
 
-        for cloud in &mut self.clouds {
-            cloud.color = color;
+        // Start with the sky already populated instead of waiting for clouds to drift in
+        for _ in 0..system.target_count(terminal_width) {
+            let cloud = system.create_random_cloud(terminal_width, terminal_height, true, rng);
+            system.clouds.push(cloud);
         }
+        system
     }
-}
 
-impl CloudSystem {
-    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
-        let mut rng = rand::rng();
-        // Add a few initial clouds
-        let count = std::cmp::max(1, terminal_width / 20);
-
-        let max_capacity = (terminal_width / 20) as usize;
-        let mut clouds = Vec::with_capacity(max_capacity);
-
-        for _ in 0..count {
-            clouds.push(Self::create_random_cloud(
-                terminal_width,
-                terminal_height,
-                true,
-                Color::White,
-                &mut rng,
-            ));
-        }
+    /// Sets the cloud cover in percent. Count, size, layering and shade all follow it;
+    /// a clear sky fades out, with the remaining clouds speeding up and drifting off.
+    pub fn set_cover(&mut self, cover_percent: f64) {
+        self.cover = (cover_percent / 100.0).clamp(0.0, 1.0) as f32;
+        self.fade.set_active(self.cover > CLEAR_SKY_COVER);
+    }
 
-        Self {
-            clouds,
-            terminal_width,
-            terminal_height,
-        }
+    /// Skips the fade in and out, for off-screen renders (see `Fade::set_instant`).
+    pub fn set_instant_fade(&mut self, instant: bool) {
+        self.fade.set_instant(instant);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.fade.is_active() || !self.clouds.is_empty()
+    }
+
+    /// Scattered clouds at low cover, enough to fill the sky in two layers when overcast.
+    fn target_count(&self, terminal_width: u16) -> usize {
+        self.fade
+            .scale((terminal_width as f32 / 12.0 * self.cover).ceil() as usize)
+    }
+
+    fn color(cover: f32, layer: Layer, palette: &Palette) -> Color {
+        let shade = match cover {
+            c if c < 0.35 => 0,
+            c if c < 0.7 => 1,
+            _ => 2,
+        } + usize::from(layer == Layer::Back);
+
+        let sky = &palette.sky;
+        [sky.cloud, sky.cloud_shade, sky.cloud_dark, sky.cloud_dark][shade]
     }
 
     fn create_random_cloud(
+        &self,
         width: u16,
         height: u16,
         random_x: bool,
-        color: Color,
         rng: &mut impl Rng,
     ) -> Cloud {
-        let shapes = CLOUD_SHAPES.get_or_init(Self::create_cloud_shapes);
+        // Heavier cover brings bigger cloud banks and a second, darker layer behind
+        let large_chance = ((self.cover - 0.4) * 1.5).clamp(0.0, 0.8);
+        let shapes = if rng.random::<f32>() < large_chance {
+            LARGE_CLOUD_SHAPES.get_or_init(Self::create_large_cloud_shapes)
+        } else {
+            CLOUD_SHAPES.get_or_init(Self::create_cloud_shapes)
+        };
+        let layer = if rng.random::<f32>() < self.cover * 0.5 {
+            Layer::Back
+        } else {
+            Layer::Front
+        };
 
         let shape_idx = (rng.random::<u32>() as usize) % shapes.len();
         let shape = shapes[shape_idx].clone();
 
-        let y_range = height / 3;
+        let y_range = (height as f32 * (0.3 + 0.2 * self.cover)) as u16;
         let y = (rng.random::<u16>() % std::cmp::max(1, y_range)) as f32;
 
         let x = if random_x {
-            (rng.random::<u16>() % width) as f32
+            (rng.random::<u16>() % width.max(1)) as f32
         } else {
             -(shape[0].len() as f32)
         };
 
-        let speed = 0.05 + (rng.random::<f32>() * 0.1);
+        let speed =
+            (0.05 + (rng.random::<f32>() * 0.1)) * if layer == Layer::Back { 0.6 } else { 1.0 };
 
         Cloud {
             x,
             y,
             speed,
             shape,
-            color,
+            layer,
         }
     }
 
@@ -121,43 +161,59 @@ impl CloudSystem {
         shapes.to_vec()
     }
 
-    pub fn update(
-        &mut self,
-        terminal_width: u16,
-        terminal_height: u16,
-        is_clear: bool,
-        cloud_color: Color,
-        rng: &mut impl Rng,
-    ) {
+    fn create_large_cloud_shapes() -> Vec<Vec<String>> {
+        let shapes = [
+            vec![
+                "        .-~~~-.  .-~~-.        ".to_string(),
+                "   .- ~(       )(      )~ -.   ".to_string(),
+                "  (                         )  ".to_string(),
+                " (                           ) ".to_string(),
+                "  `-.___.-~~-.___.-~~-.___.-'  ".to_string(),
+            ],
+            vec![
+                "         _.--._     _.-._         ".to_string(),
+                "    _.-(        )--(     `-.      ".to_string(),
+                "  (                          )_   ".to_string(),
+                " (                              ) ".to_string(),
+                "  `--.____.--~~--.____.--~~--.-'  ".to_string(),
+            ],
+        ];
+
+        shapes.to_vec()
+    }
+
+    pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
+        self.fade.update();
 
+        let speed_boost = if self.fade.is_fading_out() || !self.fade.is_active() {
+            1.0 + 3.0 * (1.0 - self.fade.level())
+        } else {
+            1.0
+        };
         for cloud in &mut self.clouds {
-            cloud.x += cloud.speed;
+            cloud.x += cloud.speed * speed_boost;
         }
 
         self.clouds.retain(|c| c.x < terminal_width as f32);
 
-        let max_clouds = if is_clear {
-            (terminal_width / 40) as usize
-        } else {
-            (terminal_width / 20) as usize
-        };
-
-        let spawn_chance = if is_clear { 0.002 } else { 0.005 };
-
-        if self.clouds.len() < max_clouds && rng.random::<f32>() < spawn_chance {
-            self.clouds.push(Self::create_random_cloud(
-                terminal_width,
-                terminal_height,
-                false,
-                cloud_color,
-                rng,
-            ));
+        let target = self.target_count(terminal_width);
+        let spawn_chance = (0.002 + 0.02 * self.cover) * self.fade.level();
+
+        // A sky far short of its cover (e.g. just turned overcast) fills in place
+        // rather than waiting minutes for clouds to drift in from the edge
+        let fill_in_place = self.clouds.len() < target / 2;
+        if self.clouds.len() < target && (fill_in_place || rng.random::<f32>() < spawn_chance) {
+            let cloud =
+                self.create_random_cloud(terminal_width, terminal_height, fill_in_place, rng);
+            self.clouds.push(cloud);
+            self.clouds.sort_by_key(|c| c.layer);
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
+        // Clouds are kept sorted back layer first, so nearer clouds draw over farther ones
         for cloud in &self.clouds {
             for (i, line) in cloud.shape.iter().enumerate() {
                 let y = cloud.y as i16 + i as i16;
@@ -168,7 +224,7 @@ impl CloudSystem {
                         std::cmp::max(0, x) as u16,
                         y as u16,
                         line,
-                        cloud.color,
+                        Self::color(self.cover, cloud.layer, palette),
                     )?;
                 }
             }
@@ -1,6 +1,7 @@
+use super::transition::{FADE_DURATION, Fade};
 use crate::render::TerminalRenderer;
+use crate::theme::Palette;
 use crate::weather::types::FogIntensity;
-use crossterm::style::Color;
 use rand::prelude::*;
 use std::collections::VecDeque;
 use std::io;
@@ -10,7 +11,7 @@ struct FogWisp {
     y: f32,
     speed_x: f32,
     character: char,
-    color: Color,
+    is_dark: bool,
     lifetime: u32,
     max_lifetime: u32,
 }
@@ -26,23 +27,12 @@ impl FogWisp {
         let chars = ['.', ',', '-', '~'];
         let char_idx = (rng.random::<u32>() as usize) % chars.len();
 
-        let colors = [
-            Color::Grey,
-            Color::DarkGrey,
-            Color::Rgb {
-                r: 120,
-                g: 120,
-                b: 120,
-            },
-        ];
-        let color_idx = (rng.random::<u32>() as usize) % colors.len();
-
         Self {
             x,
             y,
             speed_x: (rng.random::<f32>() - 0.5) * 0.15,
             character: chars[char_idx],
-            color: colors[color_idx],
+            is_dark: rng.random::<bool>(),
             lifetime: 0,
             max_lifetime: 100 + (rng.random::<u32>() % 200),
         }
@@ -66,6 +56,7 @@ pub struct FogSystem {
     terminal_height: u16,
     intensity: FogIntensity,
     spawn_timer: u32,
+    fade: Fade,
 }
 
 impl FogSystem {
@@ -82,6 +73,7 @@ impl FogSystem {
             terminal_height,
             intensity,
             spawn_timer: 0,
+            fade: Fade::new(FADE_DURATION),
         }
     }
 
@@ -89,9 +81,24 @@ impl FogSystem {
         self.intensity = intensity;
     }
 
+    /// Thickens or lifts the fog; existing wisps live out their lifetime.
+    pub fn set_active(&mut self, active: bool) {
+        self.fade.set_active(active);
+    }
+
+    /// Skips the fade in and out, for off-screen renders (see `Fade::set_instant`).
+    pub fn set_instant_fade(&mut self, instant: bool) {
+        self.fade.set_instant(instant);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.fade.is_active() || !self.wisps.is_empty()
+    }
+
     pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
+        self.fade.update();
 
         for wisp in &mut self.wisps {
             wisp.update();
@@ -104,7 +111,9 @@ impl FogSystem {
             FogIntensity::Medium => (0.6, 2),
             FogIntensity::Heavy => (1.0, 1),
         };
-        let target_count = (terminal_width as f32 * target_multiplier) as usize;
+        let target_count = self
+            .fade
+            .scale((terminal_width as f32 * target_multiplier) as usize);
 
         self.spawn_timer += 1;
         if self.spawn_timer >= spawn_delay && self.wisps.len() < target_count {
@@ -118,14 +127,19 @@ impl FogSystem {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         for wisp in &self.wisps {
             let x = wisp.x as i16;
             let y = wisp.y as i16;
 
             if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
             {
-                renderer.render_char(x as u16, y as u16, wisp.character, wisp.color)?;
+                let color = if wisp.is_dark {
+                    palette.sky.fog_dark
+                } else {
+                    palette.sky.fog
+                };
+                renderer.render_char(x as u16, y as u16, wisp.character, color)?;
             }
         }
         Ok(())
@@ -0,0 +1,171 @@
+use super::transition::{FADE_DURATION, Fade};
+use crate::render::TerminalRenderer;
+use crate::theme::Palette;
+use rand::prelude::*;
+use std::io;
+
+/// Downward acceleration per frame, so bounces arc back to the ground.
+const GRAVITY: f32 = 0.12;
+/// Share of the vertical speed kept after hitting the ground.
+const BOUNCE_DAMPING: f32 = 0.35;
+const MAX_BOUNCES: u8 = 2;
+/// Frames a settled pellet lies on the ground before melting away.
+const REST_FRAMES: std::ops::Range<u16> = 45..120;
+/// Settled pellets drawn at most, as a multiple of the terminal width.
+const MAX_PILE_PER_COLUMN: f32 = 0.6;
+/// Hail strength used until weather data says otherwise.
+const DEFAULT_STRENGTH: f32 = 0.95;
+
+struct Pellet {
+    x: f32,
+    y: f32,
+    speed_x: f32,
+    speed_y: f32,
+    /// Row the pellet lands on; spread across the ground for depth.
+    ground_y: f32,
+    bounces: u8,
+    /// Frames left on the ground once settled, `None` while airborne.
+    rest: Option<u16>,
+}
+
+pub struct HailSystem {
+    pellets: Vec<Pellet>,
+    terminal_width: u16,
+    terminal_height: u16,
+    wind_x: f32,
+    fade: Fade,
+    strength: f32,
+}
+
+impl HailSystem {
+    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+        Self {
+            pellets: Vec::with_capacity(terminal_width as usize),
+            terminal_width,
+            terminal_height,
+            wind_x: 0.0,
+            fade: Fade::new(FADE_DURATION),
+            strength: DEFAULT_STRENGTH,
+        }
+    }
+
+    /// Sets how hard it hails (0.0 to 1.0), scaling how many pellets fall.
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength.clamp(0.0, 1.0);
+    }
+
+    /// Starts or stops the hail; pellets already down melt away on their own.
+    pub fn set_active(&mut self, active: bool) {
+        self.fade.set_active(active);
+    }
+
+    /// Skips the fade in and out, for off-screen renders (see `Fade::set_instant`).
+    pub fn set_instant_fade(&mut self, instant: bool) {
+        self.fade.set_instant(instant);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.fade.is_active() || !self.pellets.is_empty()
+    }
+
+    pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
+        // Heavy pellets drift less than raindrops
+        self.wind_x = speed_kmh / 80.0 * -direction_deg.to_radians().sin();
+    }
+
+    fn spawn_pellet(&mut self, horizon_y: u16, rng: &mut impl Rng) {
+        let width = self.terminal_width as f32;
+        let ground_top = horizon_y as f32;
+        let ground_bottom = self.terminal_height.saturating_sub(1) as f32;
+
+        self.pellets.push(Pellet {
+            x: rng.random::<f32>() * width * 1.5 - width * 0.25,
+            y: 0.0,
+            speed_x: self.wind_x + (rng.random::<f32>() * 0.1 - 0.05),
+            speed_y: 1.0 + rng.random::<f32>() * 0.6,
+            ground_y: ground_top + rng.random::<f32>() * (ground_bottom - ground_top).max(0.0),
+            bounces: 0,
+            rest: None,
+        });
+    }
+
+    pub fn update(
+        &mut self,
+        terminal_width: u16,
+        terminal_height: u16,
+        horizon_y: u16,
+        rng: &mut impl Rng,
+    ) {
+        self.terminal_width = terminal_width;
+        self.terminal_height = terminal_height;
+        self.fade.update();
+
+        let airborne = self.pellets.iter().filter(|p| p.rest.is_none()).count();
+        let target_count = self
+            .fade
+            .scale((terminal_width as f32 * (0.1 + 0.4 * self.strength)) as usize);
+        if airborne < target_count {
+            let spawn_rate = self.fade.scale(1 + (3.0 * self.strength).round() as usize);
+            for _ in 0..spawn_rate {
+                self.spawn_pellet(horizon_y, rng);
+            }
+        }
+
+        self.pellets.retain_mut(|pellet| {
+            if let Some(rest) = pellet.rest.as_mut() {
+                *rest = rest.saturating_sub(1);
+                return *rest > 0;
+            }
+
+            pellet.speed_y += GRAVITY;
+            pellet.x += pellet.speed_x;
+            pellet.y += pellet.speed_y;
+
+            if pellet.y >= pellet.ground_y {
+                pellet.y = pellet.ground_y;
+                if pellet.bounces < MAX_BOUNCES {
+                    pellet.bounces += 1;
+                    pellet.speed_y = -pellet.speed_y * BOUNCE_DAMPING;
+                    pellet.speed_x = pellet.speed_x * 0.5 + (rng.random::<f32>() - 0.5) * 0.6;
+                } else {
+                    pellet.rest = Some(rng.random_range(REST_FRAMES));
+                }
+            }
+
+            pellet.x >= -10.0 && pellet.x <= terminal_width as f32 + 10.0
+        });
+
+        // Oldest settled pellets melt first when the pile gets too big
+        let max_pile = (terminal_width as f32 * MAX_PILE_PER_COLUMN) as usize;
+        let mut settled = self.pellets.iter().filter(|p| p.rest.is_some()).count();
+        if settled > max_pile {
+            self.pellets.retain(|pellet| {
+                if pellet.rest.is_some() && settled > max_pile {
+                    settled -= 1;
+                    return false;
+                }
+                true
+            });
+        }
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
+        for pellet in &self.pellets {
+            let x = pellet.x as i16;
+            let y = pellet.y as i16;
+            if x < 0 || y < 0 || x >= self.terminal_width as i16 || y >= self.terminal_height as i16
+            {
+                continue;
+            }
+
+            let (ch, color) = match pellet.rest {
+                None => ('o', palette.precipitation.hail),
+                Some(rest) if rest > 20 => ('o', palette.precipitation.hail_settled),
+                Some(_) => ('.', palette.precipitation.hail_melting),
+            };
+            renderer.render_char(x as u16, y as u16, ch, color)?;
+        }
+
+        Ok(())
+    }
+}
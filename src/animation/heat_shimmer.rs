@@ -0,0 +1,94 @@
+use super::transition::{FADE_DURATION, Fade};
+use crate::render::TerminalRenderer;
+use crossterm::style::Color;
+use rand::prelude::*;
+use std::io;
+
+/// Rows above the horizon the shimmer reaches.
+const SHIMMER_ROWS: u16 = 3;
+/// Wavering cells at full strength, per column of the terminal.
+const CELLS_PER_COLUMN: f32 = 0.15;
+
+struct Wisp {
+    x: u16,
+    y: u16,
+    age: u8,
+    max_age: u8,
+}
+
+/// Hot air wavering just above the ground on scorching days.
+pub struct HeatShimmer {
+    wisps: Vec<Wisp>,
+    fade: Fade,
+}
+
+impl HeatShimmer {
+    pub fn new() -> Self {
+        Self {
+            wisps: Vec::new(),
+            fade: Fade::new(FADE_DURATION),
+        }
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.fade.set_active(active);
+    }
+
+    /// Skips the fade in and out, for off-screen renders (see `Fade::set_instant`).
+    pub fn set_instant_fade(&mut self, instant: bool) {
+        self.fade.set_instant(instant);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.fade.is_active() || !self.wisps.is_empty()
+    }
+
+    pub fn update(&mut self, terminal_width: u16, horizon_y: u16, rng: &mut impl Rng) {
+        self.fade.update();
+
+        for wisp in &mut self.wisps {
+            wisp.age += 1;
+            // Drift sideways now and then so the air seems to ripple
+            if rng.random::<f32>() < 0.3 {
+                wisp.x = if rng.random::<bool>() {
+                    wisp.x.saturating_add(1)
+                } else {
+                    wisp.x.saturating_sub(1)
+                };
+            }
+        }
+        self.wisps
+            .retain(|wisp| wisp.age < wisp.max_age && wisp.x < terminal_width);
+
+        let target = self
+            .fade
+            .scale((terminal_width as f32 * CELLS_PER_COLUMN) as usize);
+        let lowest = horizon_y.saturating_sub(1);
+        while self.wisps.len() < target && terminal_width > 0 {
+            self.wisps.push(Wisp {
+                x: rng.random_range(0..terminal_width),
+                y: lowest.saturating_sub(rng.random_range(0..SHIMMER_ROWS)),
+                age: 0,
+                max_age: rng.random_range(6..18),
+            });
+        }
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        for wisp in &self.wisps {
+            let (ch, color) = match wisp.age * 3 / wisp.max_age {
+                0 => ('.', Color::DarkYellow),
+                1 => ('~', Color::Yellow),
+                _ => ('-', Color::DarkYellow),
+            };
+            renderer.render_char(wisp.x, wisp.y, ch, color)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for HeatShimmer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
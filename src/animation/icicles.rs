@@ -0,0 +1,116 @@
+use crate::render::TerminalRenderer;
+use crate::theme::Palette;
+use rand::prelude::*;
+use std::io;
+
+/// Rows an icicle can grow below the roof edge.
+const MAX_LENGTH: u8 = 2;
+/// Chance per frame that one icicle grows while freezing rain falls.
+const GROW_CHANCE: f32 = 0.05;
+/// Chance per frame that one icicle shrinks once it stops.
+const MELT_CHANCE: f32 = 0.03;
+
+/// Icicles hanging from the house's roof edge, built up by freezing rain and
+/// melting back once it stops.
+pub struct IcicleSystem {
+    lengths: Vec<u8>,
+    freezing: bool,
+}
+
+impl IcicleSystem {
+    pub fn new(eave_width: u16) -> Self {
+        Self {
+            lengths: vec![0; eave_width as usize],
+            freezing: false,
+        }
+    }
+
+    /// Follows the roof edge when the layout swaps the house for a smaller one.
+    pub fn set_width(&mut self, eave_width: u16) {
+        self.lengths.resize(eave_width as usize, 0);
+    }
+
+    pub fn set_freezing(&mut self, freezing: bool) {
+        self.freezing = freezing;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.freezing || self.lengths.iter().any(|&length| length > 0)
+    }
+
+    pub fn update(&mut self, rng: &mut impl Rng) {
+        if self.lengths.is_empty() {
+            return;
+        }
+
+        if self.freezing {
+            if rng.random::<f32>() < GROW_CHANCE {
+                // Neighbours of an icicle stay bare so they read as separate spikes
+                let column = (rng.random::<u32>() as usize) % self.lengths.len();
+                let crowded = |i: usize| self.lengths.get(i).is_some_and(|&length| length > 0);
+                if self.lengths[column] > 0
+                    || !(crowded(column.wrapping_sub(1)) || crowded(column + 1))
+                {
+                    let length = &mut self.lengths[column];
+                    *length = (*length + 1).min(MAX_LENGTH);
+                }
+            }
+        } else if rng.random::<f32>() < MELT_CHANCE {
+            let grown: Vec<usize> = (0..self.lengths.len())
+                .filter(|&i| self.lengths[i] > 0)
+                .collect();
+            if let Some(&column) = grown.choose(rng) {
+                self.lengths[column] -= 1;
+            }
+        }
+    }
+
+    /// Draws the icicles hanging from the roof edge starting at (`eave_x`, `eave_y`).
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        palette: &Palette,
+        eave_x: u16,
+        eave_y: u16,
+    ) -> io::Result<()> {
+        for (i, &length) in self.lengths.iter().enumerate() {
+            let x = eave_x + i as u16;
+            for row in 1..=length {
+                let ch = if row == length { 'v' } else { '|' };
+                renderer.render_char(x, eave_y + row as u16, ch, palette.precipitation.ice)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_icicles_grow_while_freezing_and_melt_after() {
+        let mut rng = rand::rng();
+        let mut icicles = IcicleSystem::new(29);
+        assert!(!icicles.is_visible());
+
+        icicles.set_freezing(true);
+        for _ in 0..2000 {
+            icicles.update(&mut rng);
+        }
+        assert!(icicles.lengths.contains(&MAX_LENGTH));
+        assert!(icicles.lengths.iter().all(|&length| length <= MAX_LENGTH));
+        assert!(
+            icicles
+                .lengths
+                .windows(2)
+                .all(|pair| pair[0] == 0 || pair[1] == 0)
+        );
+
+        icicles.set_freezing(false);
+        for _ in 0..20_000 {
+            icicles.update(&mut rng);
+        }
+        assert!(!icicles.is_visible());
+    }
+}
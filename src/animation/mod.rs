@@ -4,6 +4,9 @@ pub mod chimney;
 pub mod clouds;
 pub mod fireflies;
 pub mod fog;
+pub mod hail;
+pub mod heat_shimmer;
+pub mod icicles;
 pub mod leaves;
 pub mod moon;
 pub mod raindrops;
@@ -11,6 +14,7 @@ pub mod snow;
 pub mod stars;
 pub mod sunny;
 pub mod thunderstorm;
+pub mod transition;
 
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
@@ -19,10 +23,6 @@ use std::io;
 pub trait Animation {
     fn get_frame(&self, frame_number: usize) -> &[String];
     fn frame_count(&self) -> usize;
-
-    fn get_color(&self) -> Color {
-        Color::Reset
-    }
 }
 
 pub struct AnimationController {
@@ -39,14 +39,14 @@ impl AnimationController {
         self.current_frame
     }
 
-    pub fn render_frame<A: Animation>(
+    pub fn render_frame_colored<A: Animation>(
         &self,
         renderer: &mut TerminalRenderer,
         animation: &A,
         y_offset: u16,
+        color: Color,
     ) -> io::Result<()> {
         let frame = animation.get_frame(self.current_frame);
-        let color = animation.get_color();
         renderer.render_centered_colored(frame, y_offset, color)
     }
 
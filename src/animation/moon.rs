@@ -1,7 +1,15 @@
 use crate::render::TerminalRenderer;
-use crossterm::style::Color;
+use crate::theme::Palette;
 use std::io;
 
+/// Size of the Unicode moon disc in cells, matching the ASCII full moon.
+const DISC_RADIUS_X: f32 = 5.5;
+const DISC_RADIUS_Y: f32 = 2.75;
+/// Offset of the disc's center from the top left of the ASCII art.
+const DISC_CENTER: (f32, f32) = (9.5, 3.0);
+/// Craters as (x, y, radius) in units of the disc radius.
+const CRATERS: [(f32, f32, f32); 3] = [(-0.35, -0.3, 0.2), (0.3, 0.2, 0.25), (-0.1, 0.55, 0.15)];
+
 pub struct MoonSystem {
     phase: f64, // 0.0 = New, 0.25 = First Quarter, 0.5 = Full, 0.75 = Last Quarter
     x: u16,
@@ -17,7 +25,6 @@ impl MoonSystem {
         }
     }
 
-    #[allow(dead_code)]
     pub fn set_phase(&mut self, phase: f64) {
         self.phase = phase;
     }
@@ -27,7 +34,11 @@ impl MoonSystem {
         self.y = (terminal_height / 4).max(2);
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
+        if renderer.is_unicode() {
+            return self.render_disc(renderer, palette);
+        }
+
         let step = (self.phase * 8.0).round() as usize % 8;
 
         let art = match step {
@@ -117,13 +128,77 @@ impl MoonSystem {
 
                 if ch == '~' {
                     // Opaque Moon Body (hides stars) - Render as space but overwrite what's there
-                    renderer.render_char(x, y, ' ', Color::White)?;
+                    renderer.render_char(x, y, ' ', palette.sky.moon)?;
                 } else {
                     // Texture/Outline
-                    renderer.render_char(x, y, ch, Color::White)?;
+                    renderer.render_char(x, y, ch, palette.sky.moon)?;
                 }
             }
         }
         Ok(())
     }
+
+    /// Draws the moon in braille, with the terminator at the exact phase.
+    fn render_disc(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
+        let center_x = self.x as f32 + DISC_CENTER.0;
+        let center_y = self.y as f32 + DISC_CENTER.1;
+        let cols = (DISC_RADIUS_X * 2.0).ceil() as u16 + 1;
+        let rows = (DISC_RADIUS_Y * 2.0).ceil() as u16 + 1;
+        let left = (center_x - DISC_RADIUS_X).floor() as u16;
+        let top = (center_y - DISC_RADIUS_Y).floor() as u16;
+
+        // Two dots per cell across and four down
+        for dot_y in 0..rows * 4 {
+            for dot_x in 0..cols * 2 {
+                let x = left as f32 + (dot_x as f32 + 0.5) / 2.0;
+                let y = top as f32 + (dot_y as f32 + 0.5) / 4.0;
+                let nx = (x - center_x) / DISC_RADIUS_X;
+                let ny = (y - center_y) / DISC_RADIUS_Y;
+                if nx * nx + ny * ny > 1.0 {
+                    continue;
+                }
+                if dot_x % 2 == 0 && dot_y % 4 == 0 {
+                    // The whole disc hides the stars behind it, lit or not
+                    renderer.render_char(x as u16, y as u16, ' ', palette.sky.moon)?;
+                }
+                if is_lit(self.phase, nx, ny) && !in_crater(nx, ny) {
+                    renderer.plot_dot(x, y, palette.sky.moon)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the point (`nx`, `ny`) of a unit disc is sunlit at `phase`. The
+/// terminator is a half ellipse that sweeps from the right edge to the left.
+fn is_lit(phase: f64, nx: f32, ny: f32) -> bool {
+    let half_width = (1.0 - ny * ny).max(0.0).sqrt();
+    let terminator = (phase as f32 * std::f32::consts::TAU).cos() * half_width;
+    if phase < 0.5 {
+        nx > terminator
+    } else {
+        nx < -terminator
+    }
+}
+
+fn in_crater(nx: f32, ny: f32) -> bool {
+    CRATERS
+        .iter()
+        .any(|(cx, cy, r)| (nx - cx).powi(2) + (ny - cy).powi(2) < r * r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminator_follows_phase() {
+        // New moon is dark, full moon is lit all over
+        assert!(!is_lit(0.0, 0.5, 0.0));
+        assert!(is_lit(0.5, -0.9, 0.0) && is_lit(0.5, 0.9, 0.0));
+        // First quarter lights the right half, last quarter the left
+        assert!(is_lit(0.25, 0.5, 0.0) && !is_lit(0.25, -0.5, 0.0));
+        assert!(is_lit(0.75, -0.5, 0.0) && !is_lit(0.75, 0.5, 0.0));
+    }
 }
@@ -1,4 +1,7 @@
+use super::transition::{FADE_DURATION, Fade};
 use crate::render::TerminalRenderer;
+use crate::scene::wetness::is_puddle;
+use crate::theme::Palette;
 use crate::weather::types::RainIntensity;
 use crossterm::style::Color;
 use rand::prelude::*;
@@ -6,6 +9,10 @@ use std::collections::VecDeque;
 use std::io;
 
 const MAX_SPLASHES: usize = 100;
+/// Precipitation strength used until weather data says otherwise.
+const DEFAULT_STRENGTH: f32 = 0.45;
+/// Share of particles drawn as wet snow and ice pellets in sleet mode.
+const SLEET_ICE_SHARE: f32 = 0.45;
 
 struct Raindrop {
     x: f32,
@@ -13,8 +20,10 @@ struct Raindrop {
     speed_y: f32,
     speed_x: f32,
     character: char,
-    color: Color,
     z_index: u8,
+    is_ice: bool,
+    /// Row the drop lands on, spread across the ground for depth.
+    ground_y: f32,
 }
 
 #[derive(Clone, Copy)]
@@ -23,6 +32,8 @@ struct Splash {
     y: u16,
     timer: u8,
     max_timer: u8,
+    /// Landed in a puddle, so it spreads as rings instead of splashing.
+    is_ripple: bool,
 }
 
 pub struct RaindropSystem {
@@ -33,10 +44,20 @@ pub struct RaindropSystem {
     terminal_height: u16,
     intensity: RainIntensity,
     wind_x: f32,
+    fade: Fade,
+    strength: f32,
+    sleet: bool,
+    ground_top: u16,
+    wetness: f32,
 }
 
 impl RaindropSystem {
-    pub fn new(terminal_width: u16, terminal_height: u16, intensity: RainIntensity) -> Self {
+    pub fn new(
+        terminal_width: u16,
+        terminal_height: u16,
+        intensity: RainIntensity,
+        rng: &mut impl Rng,
+    ) -> Self {
         let drops_capacity = match intensity {
             RainIntensity::Drizzle => (terminal_width / 4) as usize,
             RainIntensity::Light => (terminal_width / 2) as usize,
@@ -52,8 +73,13 @@ impl RaindropSystem {
             terminal_height,
             intensity,
             wind_x: 0.0,
+            fade: Fade::new(FADE_DURATION),
+            strength: DEFAULT_STRENGTH,
+            sleet: false,
+            ground_top: terminal_height.saturating_sub(1),
+            wetness: 0.0,
         };
-        let wind_dir = if rand::random::<bool>() { 1.0 } else { -1.0 };
+        let wind_dir = if rng.random::<bool>() { 1.0 } else { -1.0 };
         system.set_intensity_with_dir(intensity, wind_dir);
         system
     }
@@ -74,6 +100,39 @@ impl RaindropSystem {
         self.wind_x = base_wind * direction_multiplier;
     }
 
+    /// Sets how hard it rains (0.0 to 1.0), which scales drop density, fall speed
+    /// and splashes continuously on top of the intensity's look.
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength.clamp(0.0, 1.0);
+    }
+
+    /// Mixes wet snowflakes and ice pellets in with the drops.
+    pub fn set_sleet(&mut self, sleet: bool) {
+        self.sleet = sleet;
+    }
+
+    /// Lets drops land anywhere from the horizon down, rippling the puddles that
+    /// form on ground this wet (see `scene::wetness`).
+    pub fn set_ground(&mut self, horizon_y: u16, wetness: f32) {
+        self.ground_top = horizon_y;
+        self.wetness = wetness;
+    }
+
+    /// Starts or stops the rain; drops already falling finish their fall.
+    pub fn set_active(&mut self, active: bool) {
+        self.fade.set_active(active);
+    }
+
+    /// Skips the fade in and out, for off-screen renders (see `Fade::set_instant`).
+    pub fn set_instant_fade(&mut self, instant: bool) {
+        self.fade.set_instant(instant);
+    }
+
+    /// True while rain is fading in, falling, or drops and splashes remain.
+    pub fn is_visible(&self) -> bool {
+        self.fade.is_active() || !self.drops.is_empty() || !self.splashes.is_empty()
+    }
+
     pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
         let speed_factor = speed_kmh / 40.0;
         let direction_rad = direction_deg.to_radians();
@@ -86,33 +145,12 @@ impl RaindropSystem {
             - (self.terminal_width as f32 * 0.5);
         let z_index = if rng.random::<bool>() { 1 } else { 0 };
 
-        let (speed_y, chars, color) = match self.intensity {
-            RainIntensity::Drizzle => (
-                if z_index == 1 { 0.4 } else { 0.2 },
-                vec!['.', ','],
-                if z_index == 1 {
-                    Color::Cyan
-                } else {
-                    Color::DarkGrey
-                },
-            ),
-            RainIntensity::Light => (
-                if z_index == 1 { 0.7 } else { 0.4 },
-                vec!['|', ':', '.'],
-                if z_index == 1 {
-                    Color::White
-                } else {
-                    Color::DarkGrey
-                },
-            ),
+        let (speed_y, chars) = match self.intensity {
+            RainIntensity::Drizzle => (if z_index == 1 { 0.4 } else { 0.2 }, vec!['.', ',']),
+            RainIntensity::Light => (if z_index == 1 { 0.7 } else { 0.4 }, vec!['|', ':', '.']),
             RainIntensity::Heavy => (
                 if z_index == 1 { 0.9 } else { 0.6 }, // Slightly faster than Light
                 vec!['|', ':'],                       // Vertical density
-                if z_index == 1 {
-                    Color::Cyan
-                } else {
-                    Color::DarkGrey // Blue-ish background
-                },
             ),
             RainIntensity::Storm => (
                 if z_index == 1 { 1.8 } else { 1.2 },
@@ -122,44 +160,46 @@ impl RaindropSystem {
                 } else {
                     vec!['/']
                 },
-                if z_index == 1 {
-                    Color::White
-                } else {
-                    Color::DarkGrey
-                },
             ),
         };
 
         let char_idx = (rng.random::<u32>() as usize) % chars.len();
-
-        self.drops.push(Raindrop {
+        let ground_bottom = self.terminal_height.saturating_sub(1);
+        let ground_top = self.ground_top.min(ground_bottom);
+        let ground_y = rng.random_range(ground_top..=ground_bottom) as f32;
+        let mut drop = Raindrop {
             x,
             y: 0.0,
-            speed_y: speed_y + (rng.random::<f32>() * 0.2),
+            speed_y: (speed_y + (rng.random::<f32>() * 0.2)) * (0.8 + 0.4 * self.strength),
             speed_x: self.wind_x + (rng.random::<f32>() * 0.1 - 0.05),
             character: chars[char_idx],
-            color,
             z_index,
-        });
+            is_ice: false,
+            ground_y,
+        };
+
+        if self.sleet && rng.random::<f32>() < SLEET_ICE_SHARE {
+            // Wet flakes and pellets fall slower than the rain around them
+            drop.is_ice = true;
+            drop.speed_y *= 0.6;
+            drop.character = if rng.random::<bool>() { '*' } else { 'o' };
+        }
+
+        self.drops.push(drop);
     }
 
     pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
+        self.fade.update();
 
-        let target_count = match self.intensity {
-            RainIntensity::Drizzle => (terminal_width / 4) as usize,
-            RainIntensity::Light => (terminal_width / 2) as usize,
-            RainIntensity::Heavy => terminal_width as usize,
-            RainIntensity::Storm => (terminal_width as f32 * 1.5) as usize,
-        };
+        let density = 0.1 + 1.4 * self.strength;
+        let target_count = self
+            .fade
+            .scale((terminal_width as f32 * density) as usize);
 
         if self.drops.len() < target_count {
-            let spawn_rate = match self.intensity {
-                RainIntensity::Drizzle => 1,
-                RainIntensity::Light => 2,
-                _ => 5,
-            };
+            let spawn_rate = self.fade.scale(1 + (5.0 * self.strength).round() as usize);
             for _ in 0..spawn_rate {
                 self.spawn_drop(rng);
             }
@@ -167,24 +207,27 @@ impl RaindropSystem {
 
         // Update drops
         let new_splashes = &mut self.new_splashes;
-        let splash_chance = match self.intensity {
-            RainIntensity::Drizzle => 0.1,
-            RainIntensity::Light => 0.3,
-            _ => 0.6,
-        };
+        let splash_chance = 0.05 + 0.65 * self.strength;
+        let (ground_top, wetness) = (self.ground_top, self.wetness);
 
         self.drops.retain_mut(|drop| {
             drop.y += drop.speed_y;
             drop.x += drop.speed_x;
 
             // Hit ground?
-            if drop.y >= (terminal_height - 1) as f32 {
+            if drop.y >= drop.ground_y.min((terminal_height - 1) as f32) {
                 if drop.z_index == 1 && rng.random::<f32>() < splash_chance {
+                    let (x, y) = (drop.x as u16, drop.y as u16);
+                    let is_ripple = !drop.is_ice
+                        && y >= ground_top
+                        && is_puddle(x as usize, (y - ground_top) as usize, wetness);
                     new_splashes.push_back(Splash {
-                        x: drop.x as u16,
-                        y: terminal_height - 1,
+                        x,
+                        y,
                         timer: 0,
-                        max_timer: 3,
+                        // Rings take a little longer to spread than a splash
+                        max_timer: if is_ripple { 4 } else { 3 },
+                        is_ripple,
                     });
                 }
                 return false; // Remove drop
@@ -210,7 +253,29 @@ impl RaindropSystem {
         });
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    /// Draws a drop as a short braille streak along its path, longer the
+    /// faster it falls, instead of one slanted character per cell.
+    fn render_streak(
+        &self,
+        renderer: &mut TerminalRenderer,
+        drop: &Raindrop,
+        palette: &Palette,
+    ) -> io::Result<()> {
+        let color = self.drop_color(drop, palette);
+        let length = drop.speed_y.clamp(0.25, 1.0);
+        let dots = (length * 4.0).ceil() as usize;
+        for i in 0..dots {
+            let back = i as f32 / 4.0 / drop.speed_y.max(0.25);
+            renderer.plot_dot(
+                drop.x - drop.speed_x * back,
+                drop.y - drop.speed_y * back,
+                color,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         // Render drops
         for drop in &self.drops {
             let x = drop.x as i16;
@@ -218,7 +283,13 @@ impl RaindropSystem {
 
             if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
             {
-                let ch = if self.intensity == RainIntensity::Storm
+                if renderer.is_unicode() && !drop.is_ice {
+                    self.render_streak(renderer, drop, palette)?;
+                    continue;
+                }
+                let ch = if drop.is_ice {
+                    drop.character
+                } else if self.intensity == RainIntensity::Storm
                     || self.intensity == RainIntensity::Heavy
                 {
                     if drop.speed_x > 0.5 {
@@ -231,12 +302,16 @@ impl RaindropSystem {
                 } else {
                     drop.character
                 };
-                renderer.render_char(x as u16, y as u16, ch, drop.color)?;
+                renderer.render_char(x as u16, y as u16, ch, self.drop_color(drop, palette))?;
             }
         }
 
         // Render splashes
         for splash in &self.splashes {
+            if splash.is_ripple {
+                self.render_ripple(renderer, splash, palette.precipitation.ripple)?;
+                continue;
+            }
             if splash.x < self.terminal_width && splash.y < self.terminal_height {
                 let ch = match splash.timer {
                     0 => '.',
@@ -244,10 +319,59 @@ impl RaindropSystem {
                     2 => 'O',
                     _ => ' ',
                 };
-                renderer.render_char(splash.x, splash.y, ch, Color::White)?;
+                renderer.render_char(splash.x, splash.y, ch, palette.precipitation.splash)?;
             }
         }
 
         Ok(())
     }
+
+    /// Near drops stand out from the far ones; light rain and storms in a brighter color.
+    fn drop_color(&self, drop: &Raindrop, palette: &Palette) -> Color {
+        let colors = &palette.precipitation;
+        match (drop.is_ice, drop.z_index == 1) {
+            (true, true) => colors.snow,
+            (true, false) => colors.snow_far,
+            (false, false) => colors.rain_far,
+            (false, true) => match self.intensity {
+                RainIntensity::Light | RainIntensity::Storm => colors.rain_highlight,
+                RainIntensity::Drizzle | RainIntensity::Heavy => colors.rain,
+            },
+        }
+    }
+
+    /// A ring spreading out from where a drop hit a puddle, kept within the water.
+    fn render_ripple(
+        &self,
+        renderer: &mut TerminalRenderer,
+        splash: &Splash,
+        color: Color,
+    ) -> io::Result<()> {
+        if splash.y >= self.terminal_height || splash.y < self.ground_top {
+            return Ok(());
+        }
+        let row = (splash.y - self.ground_top) as usize;
+        let in_puddle = |x: u16| {
+            x < self.terminal_width && is_puddle(x as usize, row, self.wetness)
+        };
+
+        if splash.timer <= 1 {
+            if in_puddle(splash.x) {
+                renderer.render_char(splash.x, splash.y, 'o', color)?;
+            }
+            return Ok(());
+        }
+
+        let radius = (splash.timer - 1) as u16;
+        if let Some(left) = splash.x.checked_sub(radius)
+            && in_puddle(left)
+        {
+            renderer.render_char(left, splash.y, '(', color)?;
+        }
+        let right = splash.x + radius;
+        if in_puddle(right) {
+            renderer.render_char(right, splash.y, ')', color)?;
+        }
+        Ok(())
+    }
 }
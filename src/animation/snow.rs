@@ -1,9 +1,13 @@
+use super::transition::{FADE_DURATION, Fade};
 use crate::render::TerminalRenderer;
+use crate::theme::Palette;
 use crate::weather::types::SnowIntensity;
-use crossterm::style::Color;
 use rand::prelude::*;
 use std::io;
 
+/// Snowfall strength used until weather data says otherwise.
+const DEFAULT_STRENGTH: f32 = 0.45;
+
 struct Snowflake {
     x: f32,
     y: f32,
@@ -11,7 +15,7 @@ struct Snowflake {
     speed_x: f32,
     sway_offset: f32,
     character: char,
-    color: Color,
+    is_near: bool,
 }
 
 pub struct SnowSystem {
@@ -20,10 +24,17 @@ pub struct SnowSystem {
     terminal_height: u16,
     intensity: SnowIntensity,
     wind_x: f32,
+    fade: Fade,
+    strength: f32,
 }
 
 impl SnowSystem {
-    pub fn new(terminal_width: u16, terminal_height: u16, intensity: SnowIntensity) -> Self {
+    pub fn new(
+        terminal_width: u16,
+        terminal_height: u16,
+        intensity: SnowIntensity,
+        rng: &mut impl Rng,
+    ) -> Self {
         let flakes_capacity = match intensity {
             SnowIntensity::Light => (terminal_width / 4) as usize,
             SnowIntensity::Medium => (terminal_width / 2) as usize,
@@ -36,9 +47,11 @@ impl SnowSystem {
             terminal_height,
             intensity,
             wind_x: 0.0,
+            fade: Fade::new(FADE_DURATION),
+            strength: DEFAULT_STRENGTH,
         };
         // Initialize with some default wind
-        let wind_dir = if rand::random::<bool>() { 0.2 } else { -0.2 };
+        let wind_dir = if rng.random::<bool>() { 0.2 } else { -0.2 };
         system.set_intensity_with_dir(intensity, wind_dir);
         system
     }
@@ -59,6 +72,25 @@ impl SnowSystem {
         self.wind_x = base_wind * direction_multiplier;
     }
 
+    /// Sets how hard it snows (0.0 to 1.0), scaling flake density and fall speed.
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength.clamp(0.0, 1.0);
+    }
+
+    /// Starts or stops the snowfall; flakes in the air keep drifting down.
+    pub fn set_active(&mut self, active: bool) {
+        self.fade.set_active(active);
+    }
+
+    /// Skips the fade in and out, for off-screen renders (see `Fade::set_instant`).
+    pub fn set_instant_fade(&mut self, instant: bool) {
+        self.fade.set_instant(instant);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.fade.is_active() || !self.flakes.is_empty()
+    }
+
     pub fn set_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
         let speed_factor = speed_kmh / 20.0;
         let direction_rad = direction_deg.to_radians();
@@ -84,34 +116,26 @@ impl SnowSystem {
         self.flakes.push(Snowflake {
             x,
             y: 0.0,
-            speed_y: base_speed_y + (rng.random::<f32>() * 0.05),
+            speed_y: (base_speed_y + (rng.random::<f32>() * 0.05)) * (0.8 + 0.4 * self.strength),
             speed_x: self.wind_x + (rng.random::<f32>() * 0.1 - 0.05),
             sway_offset: rng.random::<f32>() * 100.0, // Random phase for sway
             character: chars[char_idx],
-            color: if z_index == 1 {
-                Color::White
-            } else {
-                Color::DarkGrey
-            },
+            is_near: z_index == 1,
         });
     }
 
     pub fn update(&mut self, terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) {
         self.terminal_width = terminal_width;
         self.terminal_height = terminal_height;
+        self.fade.update();
 
-        let target_count = match self.intensity {
-            SnowIntensity::Light => (terminal_width / 4) as usize,
-            SnowIntensity::Medium => (terminal_width / 2) as usize,
-            SnowIntensity::Heavy => terminal_width as usize,
-        };
+        let density = 0.1 + 1.1 * self.strength;
+        let target_count = self
+            .fade
+            .scale((terminal_width as f32 * density) as usize);
 
         if self.flakes.len() < target_count {
-            let spawn_rate = match self.intensity {
-                SnowIntensity::Light => 1,
-                SnowIntensity::Medium => 2,
-                SnowIntensity::Heavy => 4,
-            };
+            let spawn_rate = self.fade.scale(1 + (3.0 * self.strength).round() as usize);
             for _ in 0..spawn_rate {
                 self.spawn_flake(rng);
             }
@@ -138,14 +162,24 @@ impl SnowSystem {
         });
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         for flake in &self.flakes {
             let x = flake.x as i16;
             let y = flake.y as i16;
 
             if x >= 0 && x < self.terminal_width as i16 && y >= 0 && y < self.terminal_height as i16
             {
-                renderer.render_char(x as u16, y as u16, flake.character, flake.color)?;
+                let color = if flake.is_near {
+                    palette.precipitation.snow
+                } else {
+                    palette.precipitation.snow_far
+                };
+                if renderer.is_unicode() && !flake.is_near {
+                    // Distant flakes become single dots that drift between cells
+                    renderer.plot_dot(flake.x, flake.y, color)?;
+                } else {
+                    renderer.render_char(x as u16, y as u16, flake.character, color)?;
+                }
             }
         }
         Ok(())
@@ -1,5 +1,5 @@
 use crate::render::TerminalRenderer;
-use crossterm::style::Color;
+use crate::theme::Palette;
 use rand::prelude::*;
 use std::io;
 
@@ -27,8 +27,7 @@ pub struct StarSystem {
 }
 
 impl StarSystem {
-    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
-        let mut rng = rand::rng();
+    pub fn new(terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) -> Self {
         let count = (terminal_width as usize * terminal_height as usize) / 80; // Density
         let mut stars = Vec::with_capacity(count);
         const MIN_DISTANCE: f32 = 3.0; // Minimum distance between stars
@@ -104,7 +103,7 @@ impl StarSystem {
         }
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         for star in &self.stars {
             let ch = if star.brightness > 0.8 {
                 '*'
@@ -114,12 +113,19 @@ impl StarSystem {
                 '.'
             };
             let color = if star.brightness > 0.6 {
-                Color::White
+                palette.sky.stars
             } else {
-                Color::DarkGrey
+                palette.sky.stars_dim
             };
 
-            renderer.render_char(star.x, star.y, ch, color)?;
+            if ch == '.' && renderer.is_unicode() {
+                // A single braille dot, placed within the cell so faint stars
+                // don't line up on the character grid
+                let (dot_x, dot_y) = star_dot_offset(star.x, star.y);
+                renderer.plot_dot(star.x as f32 + dot_x, star.y as f32 + dot_y, color)?;
+            } else {
+                renderer.render_char(star.x, star.y, ch, color)?;
+            }
         }
 
         if let Some(ref star) = self.shooting_star {
@@ -132,7 +138,21 @@ impl StarSystem {
                 && head_y >= 0
                 && head_y < self.terminal_height as i16
             {
-                renderer.render_char(head_x as u16, head_y as u16, '*', Color::White)?;
+                renderer.render_char(head_x as u16, head_y as u16, '*', palette.sky.stars)?;
+            }
+
+            if renderer.is_unicode() {
+                // A continuous streak of dots instead of spaced-out characters
+                let steps = star.length * 4;
+                for i in 1..steps {
+                    let t = i as f32 / 4.0;
+                    renderer.plot_dot(
+                        star.x - star.speed_x * t,
+                        star.y - star.speed_y * t,
+                        palette.sky.stars,
+                    )?;
+                }
+                return Ok(());
             }
 
             for i in 1..star.length {
@@ -145,7 +165,7 @@ impl StarSystem {
                     && trail_y < self.terminal_height as i16
                 {
                     let ch = if i == 1 { '+' } else { '.' };
-                    renderer.render_char(trail_x as u16, trail_y as u16, ch, Color::White)?;
+                    renderer.render_char(trail_x as u16, trail_y as u16, ch, palette.sky.stars)?;
                 }
             }
             }
@@ -154,3 +174,10 @@ impl StarSystem {
         Ok(())
     }
 }
+
+/// Where in its cell a faint star's braille dot sits, in fractions of a cell.
+fn star_dot_offset(x: u16, y: u16) -> (f32, f32) {
+    let col = (x as usize + y as usize) % 2;
+    let row = (x as usize * 3 + y as usize) % 4;
+    (col as f32 * 0.5 + 0.25, row as f32 * 0.25 + 0.125)
+}
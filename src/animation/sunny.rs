@@ -1,5 +1,4 @@
 use super::Animation;
-use crossterm::style::Color;
 
 pub struct SunnyAnimation {
     frames: Vec<Vec<String>>,
@@ -49,10 +48,6 @@ impl Animation for SunnyAnimation {
     fn frame_count(&self) -> usize {
         self.frames.len()
     }
-
-    fn get_color(&self) -> Color {
-        Color::Yellow
-    }
 }
 
 impl Default for SunnyAnimation {
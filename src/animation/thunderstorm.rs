@@ -1,5 +1,5 @@
 use crate::render::TerminalRenderer;
-use crossterm::style::Color;
+use crate::theme::Palette;
 use rand::prelude::*;
 use std::collections::VecDeque;
 use std::io;
@@ -32,7 +32,7 @@ pub struct ThunderstormSystem {
 }
 
 impl ThunderstormSystem {
-    pub fn new(terminal_width: u16, terminal_height: u16) -> Self {
+    pub fn new(terminal_width: u16, terminal_height: u16, rng: &mut impl Rng) -> Self {
         Self {
             bolts: VecDeque::with_capacity(MAX_BOLTS),
             state: LightningState::Idle,
@@ -40,7 +40,7 @@ impl ThunderstormSystem {
             terminal_width,
             terminal_height,
             flash_active: false,
-            next_strike_in: 60 + (rand::random::<u16>() % 120), // Random start delay
+            next_strike_in: 60 + (rng.random::<u16>() % 120), // Random start delay
         }
     }
 
@@ -155,11 +155,11 @@ impl ThunderstormSystem {
         self.flash_active
     }
 
-    pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+    pub fn render(&self, renderer: &mut TerminalRenderer, palette: &Palette) -> io::Result<()> {
         let color = if self.flash_active {
-            Color::White
+            palette.precipitation.lightning_flash
         } else {
-            Color::Yellow
+            palette.precipitation.lightning
         };
 
         for bolt in &self.bolts {
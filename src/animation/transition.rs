@@ -0,0 +1,163 @@
+use std::time::{Duration, Instant};
+
+/// How long effects take to fade fully in or out when the weather changes.
+pub const FADE_DURATION: Duration = Duration::from_secs(4);
+
+/// Longest step a single update may take, so an effect that was not updated for
+/// a while (e.g. hidden) still ramps in instead of jumping straight to full.
+const MAX_STEP: Duration = Duration::from_millis(100);
+
+/// A level between 0.0 and 1.0 that ramps linearly toward its target over `duration`.
+/// Particle systems scale their spawn rates and counts by it so conditions cross-fade.
+#[derive(Debug, Clone)]
+pub struct Fade {
+    level: f32,
+    target: f32,
+    duration: Duration,
+    last_update: Option<Instant>,
+    instant: bool,
+}
+
+impl Fade {
+    /// Starts fully faded out.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            level: 0.0,
+            target: 0.0,
+            duration,
+            last_update: None,
+            instant: false,
+        }
+    }
+
+    /// Starts fully faded in.
+    pub fn full(duration: Duration) -> Self {
+        Self {
+            level: 1.0,
+            target: 1.0,
+            ..Self::new(duration)
+        }
+    }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.target = if active { 1.0 } else { 0.0 };
+    }
+
+    /// Makes `update` jump straight to the target, so that renders without a
+    /// wall clock to follow (snapshots, tests) show effects at full strength
+    /// from the first frame.
+    pub fn set_instant(&mut self, instant: bool) {
+        self.instant = instant;
+    }
+
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    pub fn is_fading_out(&self) -> bool {
+        self.target < self.level
+    }
+
+    /// True while the effect is visible or about to fade in.
+    pub fn is_active(&self) -> bool {
+        self.level > 0.0 || self.target > 0.0
+    }
+
+    pub fn update(&mut self) {
+        if self.instant {
+            self.level = self.target;
+            return;
+        }
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map_or(Duration::ZERO, |last| now.duration_since(last));
+        self.last_update = Some(now);
+        self.advance(elapsed);
+    }
+
+    pub fn advance(&mut self, elapsed: Duration) {
+        if self.duration.is_zero() {
+            self.level = self.target;
+            return;
+        }
+
+        let step = elapsed.min(MAX_STEP).as_secs_f32() / self.duration.as_secs_f32();
+        self.level = if self.level < self.target {
+            (self.level + step).min(self.target)
+        } else {
+            (self.level - step).max(self.target)
+        };
+    }
+
+    /// Scales a full-strength count by the current level, rounding up so an effect
+    /// that has started fading in always gets at least one particle.
+    pub fn scale(&self, count: usize) -> usize {
+        (count as f32 * self.level).ceil() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step_for(fade: &mut Fade, total: Duration) {
+        let mut elapsed = Duration::ZERO;
+        while elapsed < total {
+            fade.advance(MAX_STEP);
+            elapsed += MAX_STEP;
+        }
+    }
+
+    #[test]
+    fn test_fade_ramps_in_and_out() {
+        let mut fade = Fade::new(Duration::from_secs(2));
+        assert!(!fade.is_active());
+
+        fade.set_active(true);
+        assert!(fade.is_active());
+        step_for(&mut fade, Duration::from_secs(1));
+        assert!((fade.level() - 0.5).abs() < 1e-4);
+        step_for(&mut fade, Duration::from_secs(5));
+        assert_eq!(fade.level(), 1.0);
+
+        fade.set_active(false);
+        assert!(fade.is_fading_out());
+        step_for(&mut fade, Duration::from_secs(2));
+        assert_eq!(fade.level(), 0.0);
+        assert!(!fade.is_active());
+    }
+
+    #[test]
+    fn test_long_gap_is_capped() {
+        let mut fade = Fade::new(Duration::from_secs(4));
+        fade.set_active(true);
+        fade.advance(Duration::from_secs(600));
+        assert!(fade.level() < 0.05);
+    }
+
+    #[test]
+    fn test_scale_rounds_up() {
+        let mut fade = Fade::new(Duration::from_secs(1));
+        assert_eq!(fade.scale(80), 0);
+        fade.set_active(true);
+        fade.advance(Duration::from_millis(10));
+        assert_eq!(fade.scale(80), 1);
+        assert_eq!(Fade::full(FADE_DURATION).scale(80), 80);
+    }
+
+    #[test]
+    fn test_instant_fade_jumps_to_target() {
+        let mut fade = Fade::new(FADE_DURATION);
+        fade.set_instant(true);
+        fade.set_active(true);
+        fade.update();
+        assert_eq!(fade.level(), 1.0);
+
+        // Other fades keep following the clock
+        let mut other = Fade::new(FADE_DURATION);
+        other.set_active(true);
+        other.update();
+        assert_eq!(other.level(), 0.0);
+    }
+}
@@ -1,14 +1,18 @@
 use crate::animation::{
-    AnimationController, airplanes::AirplaneSystem, birds::BirdSystem, chimney::ChimneySmoke,
-    clouds::CloudSystem, fireflies::FireflySystem, fog::FogSystem, leaves::FallingLeaves,
-    moon::MoonSystem, raindrops::RaindropSystem, snow::SnowSystem, stars::StarSystem,
-    sunny::SunnyAnimation, thunderstorm::ThunderstormSystem,
+    AnimationController, airplanes::AirplaneSystem, birds::BirdSystem,
+    chimney::ChimneySmoke, clouds::CloudSystem, fireflies::FireflySystem, fog::FogSystem,
+    hail::HailSystem, heat_shimmer::HeatShimmer, icicles::IcicleSystem, leaves::FallingLeaves, moon::MoonSystem,
+    raindrops::RaindropSystem, snow::SnowSystem, stars::StarSystem, sunny::SunnyAnimation,
+    thunderstorm::ThunderstormSystem,
+    transition::{FADE_DURATION, Fade},
 };
 use crate::app_state::AppState;
 use crate::render::TerminalRenderer;
-use crate::scene::WorldScene;
-use crate::scene::house::House;
-use crate::weather::{FogIntensity, RainIntensity, SnowIntensity, WeatherConditions};
+use crate::scene::layout::{HousePlacement, SceneLayout};
+use crate::season::Season;
+use crate::weather::{
+    FogIntensity, RainIntensity, SnowIntensity, WeatherConditions, WeatherData,
+};
 use crossterm::style::Color;
 use std::io;
 use std::time::{Duration, Instant};
@@ -19,6 +23,9 @@ pub struct AnimationManager {
     raindrop_system: RaindropSystem,
     snow_system: SnowSystem,
     fog_system: FogSystem,
+    hail_system: HailSystem,
+    heat_shimmer: HeatShimmer,
+    icicles: IcicleSystem,
     thunderstorm_system: ThunderstormSystem,
     cloud_system: CloudSystem,
     bird_system: BirdSystem,
@@ -29,33 +36,90 @@ pub struct AnimationManager {
     firefly_system: FireflySystem,
     falling_leaves: FallingLeaves,
     sunny_animation: SunnyAnimation,
+    sun_fade: Fade,
     animation_controller: AnimationController,
     last_frame_time: Instant,
     show_leaves: bool,
+    season: Season,
+    ground_wetness: f32,
 }
 
 impl AnimationManager {
-    pub fn new(term_width: u16, term_height: u16, show_leaves: bool) -> Self {
+    pub fn new(
+        term_width: u16,
+        term_height: u16,
+        show_leaves: bool,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
         Self {
-            raindrop_system: RaindropSystem::new(term_width, term_height, RainIntensity::Light),
-            snow_system: SnowSystem::new(term_width, term_height, SnowIntensity::Light),
+            raindrop_system: RaindropSystem::new(
+                term_width,
+                term_height,
+                RainIntensity::Light,
+                rng,
+            ),
+            snow_system: SnowSystem::new(term_width, term_height, SnowIntensity::Light, rng),
             fog_system: FogSystem::new(term_width, term_height, FogIntensity::Light),
-            thunderstorm_system: ThunderstormSystem::new(term_width, term_height),
-            cloud_system: CloudSystem::new(term_width, term_height),
+            hail_system: HailSystem::new(term_width, term_height),
+            heat_shimmer: HeatShimmer::new(),
+            icicles: IcicleSystem::new(Self::house(term_width, term_height).eave().2),
+            thunderstorm_system: ThunderstormSystem::new(term_width, term_height, rng),
+            cloud_system: CloudSystem::new(term_width, term_height, rng),
             bird_system: BirdSystem::new(term_width, term_height),
             airplane_system: AirplaneSystem::new(term_width, term_height),
-            star_system: StarSystem::new(term_width, term_height),
+            star_system: StarSystem::new(term_width, term_height, rng),
             moon_system: MoonSystem::new(term_width, term_height),
             chimney_smoke: ChimneySmoke::new(),
             firefly_system: FireflySystem::new(term_width, term_height),
-            falling_leaves: FallingLeaves::new(term_width, term_height),
+            falling_leaves: FallingLeaves::new(term_width, term_height, rng),
             sunny_animation: SunnyAnimation::new(),
+            sun_fade: Fade::new(FADE_DURATION),
             animation_controller: AnimationController::new(),
             last_frame_time: Instant::now(),
             show_leaves,
+            season: Season::Summer,
+            ground_wetness: 0.0,
         }
     }
 
+    /// Syncs precipitation, fog, wind, cloud cover and moon phase with newly received weather data.
+    pub fn update_weather(&mut self, weather: &WeatherData) {
+        self.update_rain_intensity(weather.condition.rain_intensity());
+        self.update_snow_intensity(weather.condition.snow_intensity());
+        self.update_fog_intensity(weather.condition.fog_intensity());
+        self.cloud_system.set_cover(weather.cloud_cover);
+        let strength = weather.precipitation_strength();
+        self.raindrop_system.set_strength(strength);
+        self.snow_system.set_strength(strength);
+        self.hail_system.set_strength(strength);
+        self.update_wind(weather.wind_speed as f32, weather.wind_direction as f32);
+        if let Some(phase) = weather.moon_phase {
+            self.moon_system.set_phase(phase);
+        }
+    }
+
+    /// Leaves fall on their own in autumn, and all year with `--leaves`.
+    pub fn set_season(&mut self, season: Season) {
+        self.season = season;
+    }
+
+    /// Makes every effect skip its fade and jump to full strength or off, for
+    /// off-screen renders that should not open on a half-faded scene.
+    pub fn set_instant_fades(&mut self, instant: bool) {
+        self.raindrop_system.set_instant_fade(instant);
+        self.snow_system.set_instant_fade(instant);
+        self.fog_system.set_instant_fade(instant);
+        self.hail_system.set_instant_fade(instant);
+        self.heat_shimmer.set_instant_fade(instant);
+        self.cloud_system.set_instant_fade(instant);
+        self.sun_fade.set_instant(instant);
+    }
+
+    /// How wet the scene's ground is, so rain ripples its puddles.
+    pub fn set_ground_wetness(&mut self, wetness: f32) {
+        self.ground_wetness = wetness;
+    }
+
     pub fn update_rain_intensity(&mut self, intensity: RainIntensity) {
         self.raindrop_system.set_intensity(intensity);
     }
@@ -67,6 +131,7 @@ impl AnimationManager {
     pub fn update_wind(&mut self, speed_kmh: f32, direction_deg: f32) {
         self.raindrop_system.set_wind(speed_kmh, direction_deg);
         self.snow_system.set_wind(speed_kmh, direction_deg);
+        self.hail_system.set_wind(speed_kmh, direction_deg);
     }
 
     pub fn update_fog_intensity(&mut self, intensity: FogIntensity) {
@@ -83,14 +148,14 @@ impl AnimationManager {
         mut rng: &mut impl rand::Rng,
     ) -> io::Result<()> {
         // Calculate horizon_y early so it's available for all systems
-        let ground_height = WorldScene::GROUND_HEIGHT;
-        let horizon_y = term_height.saturating_sub(ground_height);
+        let horizon_y = SceneLayout::new(term_width, term_height).horizon_y;
+        let palette = renderer.palette(conditions.is_day);
 
         if !conditions.is_day {
             self.star_system.update(term_width, term_height, &mut rng);
-            self.star_system.render(renderer)?;
+            self.star_system.render(renderer, &palette)?;
             self.moon_system.update(term_width, term_height);
-            self.moon_system.render(renderer)?;
+            self.moon_system.render(renderer, &palette)?;
 
             if state.should_show_fireflies() {
                 self.firefly_system
@@ -99,44 +164,42 @@ impl AnimationManager {
             }
         }
 
-        if !conditions.is_raining
-            && !conditions.is_thunderstorm
-            && !conditions.is_snowing
-            && conditions.is_day
-        {
+        let is_precipitating =
+            conditions.is_raining || conditions.is_thunderstorm || conditions.is_snowing;
+
+        // Birds already in flight finish crossing the sky when the weather turns
+        self.bird_system
+            .set_spawning(!is_precipitating && conditions.is_day);
+        if self.bird_system.is_visible() {
             self.bird_system.update(term_width, term_height, &mut rng);
-            self.bird_system.render(renderer)?;
+            self.bird_system.render(renderer, &palette)?;
         }
 
-        if state.should_show_sun()
-            && !conditions.is_raining
-            && !conditions.is_thunderstorm
-            && !conditions.is_snowing
-        {
+        self.sun_fade
+            .set_active(state.should_show_sun() && !is_precipitating);
+        self.sun_fade.update();
+        if self.sun_fade.level() > 0.0 {
             let animation_y = if term_height > 20 { 3 } else { 2 };
-            self.animation_controller
-                .render_frame(renderer, &self.sunny_animation, animation_y)?;
+            let color = Self::sun_color(self.sun_fade.level(), palette.sky.sun);
+            self.animation_controller.render_frame_colored(
+                renderer,
+                &self.sunny_animation,
+                animation_y,
+                color,
+            )?;
         }
 
-        if conditions.is_cloudy
-            || (!conditions.is_raining && !conditions.is_thunderstorm && !conditions.is_snowing)
-        {
-            let (is_clear, cloud_color) = if let Some(weather) = &state.current_weather {
-                match weather.condition {
-                    crate::weather::WeatherCondition::Clear => (true, Color::White),
-                    crate::weather::WeatherCondition::PartlyCloudy => (false, Color::Grey),
-                    _ => (false, Color::DarkGrey),
-                }
-            } else {
-                (false, Color::DarkGrey)
-            };
-
-            if conditions.is_cloudy || is_clear {
-                self.cloud_system.set_cloud_color(is_clear);
-                self.cloud_system
-                    .update(term_width, term_height, is_clear, cloud_color, &mut rng);
-                self.cloud_system.render(renderer)?;
-            }
+        if self.cloud_system.is_visible() {
+            self.cloud_system.update(term_width, term_height, &mut rng);
+            self.cloud_system.render(renderer, &palette)?;
+        }
+
+        // Behind the house and trees, like hot air over the distant fields
+        self.heat_shimmer
+            .set_active(state.should_show_heat_shimmer());
+        if self.heat_shimmer.is_visible() {
+            self.heat_shimmer.update(term_width, horizon_y, &mut rng);
+            self.heat_shimmer.render(renderer)?;
         }
 
         if !conditions.is_raining
@@ -152,32 +215,54 @@ impl AnimationManager {
         Ok(())
     }
 
+    /// The house with the chimney and roof edge, matching where `WorldScene` draws it.
+    fn house(term_width: u16, term_height: u16) -> HousePlacement {
+        SceneLayout::new(term_width, term_height).house
+    }
+
     pub fn render_chimney_smoke(
         &mut self,
         renderer: &mut TerminalRenderer,
-        conditions: &WeatherConditions,
+        state: &AppState,
         term_width: u16,
         term_height: u16,
         mut rng: &mut impl rand::Rng,
     ) -> io::Result<()> {
-        if conditions.is_raining || conditions.is_thunderstorm {
+        self.chimney_smoke
+            .set_spawning(state.should_show_chimney_smoke());
+        if !self.chimney_smoke.is_visible() {
             return Ok(());
         }
-        let ground_height = WorldScene::GROUND_HEIGHT;
-        let horizon_y = term_height.saturating_sub(ground_height);
-        let house_width = House::WIDTH;
-        let house_height = House::HEIGHT;
-        let house_x = (term_width / 2).saturating_sub(house_width / 2);
-        let house_y = horizon_y.saturating_sub(house_height);
-        let chimney_x = house_x + House::CHIMNEY_X_OFFSET;
-        let chimney_y = house_y;
+        let (chimney_x, chimney_y) = Self::house(term_width, term_height).chimney();
 
         self.chimney_smoke.update(chimney_x, chimney_y, &mut rng);
-        self.chimney_smoke.render(renderer)?;
+        let palette = renderer.palette(state.weather_conditions.is_day);
+        self.chimney_smoke.render(renderer, &palette)?;
 
         Ok(())
     }
 
+    /// Icicles hanging from the roof edge, grown by freezing rain.
+    pub fn render_icicles(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        conditions: &WeatherConditions,
+        term_width: u16,
+        term_height: u16,
+        mut rng: &mut impl rand::Rng,
+    ) -> io::Result<()> {
+        self.icicles.set_freezing(conditions.is_freezing_rain);
+        if !self.icicles.is_visible() {
+            return Ok(());
+        }
+        let (eave_x, eave_y, eave_width) = Self::house(term_width, term_height).eave();
+
+        self.icicles.set_width(eave_width);
+        self.icicles.update(&mut rng);
+        let palette = renderer.palette(conditions.is_day);
+        self.icicles.render(renderer, &palette, eave_x, eave_y)
+    }
+
     pub fn render_foreground(
         &mut self,
         renderer: &mut TerminalRenderer,
@@ -186,33 +271,59 @@ impl AnimationManager {
         term_height: u16,
         mut rng: &mut impl rand::Rng,
     ) -> io::Result<()> {
-        if conditions.is_thunderstorm {
+        // Systems fade in and out on their own, so several can be visible at once
+        // while one condition hands over to the next
+        // Sleet falls through the rain system, mixed with wet snow and ice pellets
+        self.raindrop_system.set_active(
+            conditions.is_raining || conditions.is_thunderstorm || conditions.is_sleet,
+        );
+        self.raindrop_system.set_sleet(conditions.is_sleet);
+        self.snow_system.set_active(
+            conditions.is_snowing
+                && !conditions.is_raining
+                && !conditions.is_thunderstorm
+                && !conditions.is_sleet,
+        );
+        self.hail_system.set_active(conditions.is_hail);
+        self.fog_system.set_active(conditions.is_foggy);
+
+        let horizon_y = SceneLayout::new(term_width, term_height).horizon_y;
+        let palette = renderer.palette(conditions.is_day);
+        if self.raindrop_system.is_visible() {
+            self.raindrop_system
+                .set_ground(horizon_y, self.ground_wetness);
             self.raindrop_system
                 .update(term_width, term_height, &mut rng);
-            self.raindrop_system.render(renderer)?;
+            self.raindrop_system.render(renderer, &palette)?;
+        }
+
+        if self.hail_system.is_visible() {
+            self.hail_system
+                .update(term_width, term_height, horizon_y, &mut rng);
+            self.hail_system.render(renderer, &palette)?;
+        }
 
+        if conditions.is_thunderstorm {
             self.thunderstorm_system
                 .update(term_width, term_height, &mut rng);
-            self.thunderstorm_system.render(renderer)?;
+            self.thunderstorm_system.render(renderer, &palette)?;
 
             if self.thunderstorm_system.is_flashing() {
                 renderer.flash_screen()?;
             }
-        } else if conditions.is_raining {
-            self.raindrop_system
-                .update(term_width, term_height, &mut rng);
-            self.raindrop_system.render(renderer)?;
-        } else if conditions.is_snowing {
+        }
+
+        if self.snow_system.is_visible() {
             self.snow_system.update(term_width, term_height, &mut rng);
-            self.snow_system.render(renderer)?;
+            self.snow_system.render(renderer, &palette)?;
         }
 
-        if conditions.is_foggy {
+        if self.fog_system.is_visible() {
             self.fog_system.update(term_width, term_height, &mut rng);
-            self.fog_system.render(renderer)?;
+            self.fog_system.render(renderer, &palette)?;
         }
 
-        if self.show_leaves
+        if (self.show_leaves || self.season == Season::Autumn)
             && !conditions.is_raining
             && !conditions.is_thunderstorm
             && !conditions.is_snowing
@@ -225,14 +336,76 @@ impl AnimationManager {
         Ok(())
     }
 
-    pub fn update_sunny_animation(&mut self, conditions: &WeatherConditions) {
-        if !conditions.is_raining
-            && !conditions.is_thunderstorm
-            && !conditions.is_snowing
-            && self.last_frame_time.elapsed() >= FRAME_DELAY
-        {
+    /// Dims the sun through darker shades while it fades out (or in).
+    fn sun_color(level: f32, full: Color) -> Color {
+        if level > 0.66 {
+            full
+        } else if level > 0.33 {
+            Color::DarkYellow
+        } else {
+            Color::DarkGrey
+        }
+    }
+
+    pub fn update_sunny_animation(&mut self) {
+        if self.sun_fade.level() > 0.0 && self.last_frame_time.elapsed() >= FRAME_DELAY {
             self.animation_controller.next_frame(&self.sunny_animation);
             self.last_frame_time = Instant::now();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::{WeatherLocation, WeatherUnits};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn render_night(seed: u64) -> String {
+        let (width, height) = (80, 24);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut renderer = TerminalRenderer::headless(width, height);
+        let mut animations = AnimationManager::new(width, height, false, &mut rng);
+        let location = WeatherLocation {
+            latitude: 52.52,
+            longitude: 13.41,
+            elevation: None,
+        };
+        let state = AppState::new(location, false, WeatherUnits::default());
+        let conditions = WeatherConditions {
+            is_day: false,
+            ..WeatherConditions::default()
+        };
+
+        for _ in 0..30 {
+            renderer.clear().unwrap();
+            animations
+                .render_background(&mut renderer, &conditions, &state, width, height, &mut rng)
+                .unwrap();
+        }
+        renderer.to_text()
+    }
+
+    #[test]
+    fn test_same_seed_plays_out_the_same() {
+        assert_eq!(render_night(7), render_night(7));
+        assert_ne!(render_night(7), render_night(8));
+    }
+
+    #[test]
+    fn test_instant_fades_turn_back_off() {
+        let mut animations = AnimationManager::new(80, 24, false, &mut StdRng::seed_from_u64(1));
+        animations.set_instant_fades(true);
+        animations.sun_fade.set_active(true);
+        animations.sun_fade.update();
+        assert_eq!(animations.sun_fade.level(), 1.0);
+
+        // Turned back off, the sun fades out on the clock again
+        animations.set_instant_fades(false);
+        animations.sun_fade.set_active(false);
+        animations.sun_fade.update();
+        assert_eq!(animations.sun_fade.level(), 1.0);
+        assert!(animations.sun_fade.is_fading_out());
+    }
+}
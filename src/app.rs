@@ -1,15 +1,25 @@
 use crate::animation_manager::AnimationManager;
 use crate::app_state::AppState;
+use crate::cache;
 use crate::config::Config;
-use crate::error::WeatherError;
-use crate::render::TerminalRenderer;
+use crate::error::{HistoryError, WeatherError};
+use crate::history::{self, HistoryRecord, HistoryStore};
+use crate::record::Recorder;
+use crate::render::{TerminalRenderer, display_width};
+use crate::replay::ReplayTimeline;
 use crate::scene::WorldScene;
+use crate::season::Season;
+use crate::simulate::TimeOfDay;
 use crate::shell::{key_event_to_bytes, ShellManager};
 use crate::weather::{
     create_provider, WeatherClient, WeatherCondition, WeatherData, WeatherLocation,
 };
+use chrono::{Local, NaiveTime, TimeZone, Timelike};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
-use std::io;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::borrow::Cow;
+use std::io::{self, Write};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -17,9 +27,18 @@ use tokio::sync::mpsc;
 const REFRESH_INTERVAL: Duration = Duration::from_secs(300);
 const INPUT_POLL_FPS: u64 = 30;
 const FRAME_DURATION: Duration = Duration::from_millis(1000 / INPUT_POLL_FPS);
+/// How long recordings and snapshots wait for live weather before drawing without it.
+const OFFSCREEN_WEATHER_WAIT: Duration = Duration::from_secs(30);
+/// Frames run before a snapshot is taken, so rain and snow have filled the sky.
+const SNAPSHOT_WARM_UP_FRAMES: usize = 90;
+
+/// The HUD line without its "Press 'q' to quit" part.
+fn without_quit_hint(info: &str) -> String {
+    info.replace(" | Press 'q' to quit", "")
+        .replace("Press 'q' to quit", "")
+}
 
 fn generate_offline_weather(rng: &mut impl rand::Rng) -> WeatherData {
-    use chrono::{Local, Timelike};
     use rand::RngExt;
 
     let now = Local::now();
@@ -53,6 +72,7 @@ fn generate_offline_weather(rng: &mut impl rand::Rng) -> WeatherData {
         is_day,
         moon_phase: Some(0.5),
         timestamp: now.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        precipitation_level: None,
     }
 }
 
@@ -61,7 +81,24 @@ pub struct App {
     animations: AnimationManager,
     scene: WorldScene,
     weather_receiver: mpsc::Receiver<Result<WeatherData, WeatherError>>,
+    weather_client: Option<WeatherClient>,
+    cache_key: Option<cache::WeatherCacheKey>,
+    history: Option<HistoryStore>,
+    history_retention_secs: u64,
+    /// Fixed season from `--season`; otherwise it follows the date and latitude.
+    season_override: Option<Season>,
+    /// Paint the sky as a background gradient instead of leaving the terminal's own.
+    sky_fill: bool,
+    /// Clock time of the simulated weather, which decides when the sky turns to dusk.
+    simulated_time: Option<NaiveTime>,
+    /// Playing back `--replay` frames, whose times of day the wall clock knows nothing about.
+    is_replay: bool,
+    /// The one source of randomness for the animations and offline weather, so
+    /// that a run started with `--seed` can be replayed.
+    rng: StdRng,
     hide_hud: bool,
+    /// Drawing to a file instead of a terminal, so there are no keys to mention.
+    offscreen: bool,
     provider_name: String,
     shell_manager: Option<ShellManager>,
     background_mode: bool,
@@ -93,8 +130,9 @@ impl App {
     pub fn new(
         config: &Config,
         simulate_condition: Option<String>,
-        simulate_night: bool,
+        replay: Option<ReplayTimeline>,
         show_leaves: bool,
+        season_override: Option<Season>,
         term_width: u16,
         term_height: u16,
     ) -> Result<Self, WeatherError> {
@@ -105,10 +143,22 @@ impl App {
         };
 
         let mut state = AppState::new(location, config.location.hide, config.units);
-        let mut animations = AnimationManager::new(term_width, term_height, show_leaves);
+        let mut rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rand::rng()),
+        };
+        let mut animations = AnimationManager::new(term_width, term_height, show_leaves, &mut rng);
         let scene = WorldScene::new(term_width, term_height);
 
         let (tx, rx) = mpsc::channel(1);
+        let mut weather_client = None;
+        let mut cache_key = None;
+        let mut history = None;
+        let is_replay = simulate_condition.is_none() && replay.is_some();
+        let simulated_time = match config.simulate.time_of_day {
+            Some(TimeOfDay::Clock(time)) if simulate_condition.is_some() => Some(time),
+            _ => None,
+        };
 
         // Set provider name based on config
         let mut provider_name = match config.weather.provider.to_lowercase().as_str() {
@@ -126,40 +176,24 @@ impl App {
                         WeatherCondition::Clear
                     });
 
-            let weather = WeatherData {
-                condition: simulated_condition,
-                temperature: 20.0,
-                apparent_temperature: 19.0,
-                humidity: 65.0,
-                precipitation: if simulated_condition.is_raining() {
-                    2.5
-                } else {
-                    0.0
-                },
-                wind_speed: if simulated_condition.is_thunderstorm() {
-                    45.0
-                } else {
-                    10.0
-                },
-                wind_direction: 225.0,
-                cloud_cover: 50.0,
-                pressure: 1013.0,
-                visibility: Some(10000.0),
-                is_day: !simulate_night,
-                moon_phase: Some(0.5),
-                timestamp: "simulated".to_string(),
-            };
-
-            let rain_intensity = weather.condition.rain_intensity();
-            let snow_intensity = weather.condition.snow_intensity();
-
-            let wind_speed = weather.wind_speed;
-            let wind_direction = weather.wind_direction;
+            let weather = config.simulate.weather(simulated_condition);
 
+            animations.update_weather(&weather);
             state.update_weather(weather);
-            animations.update_rain_intensity(rain_intensity);
-            animations.update_snow_intensity(snow_intensity);
-            animations.update_wind(wind_speed as f32, wind_direction as f32);
+        } else if let Some(timeline) = replay {
+            // Frames go through the same channel as live fetches so the run loop
+            // applies them exactly like provider data
+            provider_name = format!("replay of {}", timeline.source());
+            let schedule = timeline.schedule();
+
+            tokio::spawn(async move {
+                for (wait, weather) in schedule {
+                    tokio::time::sleep(wait).await;
+                    if tx.send(Ok(weather)).await.is_err() {
+                        break;
+                    }
+                }
+            });
         } else {
             let provider = match create_provider(&config.weather) {
                 Ok(p) => p,
@@ -170,12 +204,35 @@ impl App {
                     Arc::new(crate::weather::OpenMeteoProvider::new())
                 }
             };
-            let weather_client = WeatherClient::new(provider, REFRESH_INTERVAL);
             let units = config.units;
+            cache_key = Some(cache::WeatherCacheKey::new(
+                location.latitude,
+                location.longitude,
+                provider.id(),
+                units,
+            ));
+            let mut client = WeatherClient::new(provider, REFRESH_INTERVAL)
+                .with_retry_policy(config.retry.clone());
+            if config.history.enabled {
+                let path = config
+                    .history
+                    .path
+                    .clone()
+                    .or_else(HistoryStore::default_path);
+                match path {
+                    Some(path) => {
+                        let store = HistoryStore::new(path);
+                        client = client.with_history(store.clone());
+                        history = Some(store);
+                    }
+                    None => eprintln!("History disabled: {}", HistoryError::NoDataDir),
+                }
+            }
+            weather_client = Some(client.clone());
 
             tokio::spawn(async move {
                 loop {
-                    let result = weather_client.get_current_weather(&location, &units).await;
+                    let result = client.get_current_weather(&location, &units).await;
                     if tx.send(result).await.is_err() {
                         break;
                     }
@@ -206,7 +263,17 @@ impl App {
             animations,
             scene,
             weather_receiver: rx,
+            weather_client,
+            cache_key,
+            history,
+            history_retention_secs: config.history.retention_days.saturating_mul(24 * 3600),
+            season_override,
+            sky_fill: config.sky_fill,
+            simulated_time,
+            is_replay,
+            rng,
             hide_hud: config.hide_hud,
+            offscreen: false,
             provider_name,
             shell_manager,
             background_mode,
@@ -216,126 +283,14 @@ impl App {
     }
 
     pub async fn run(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
-        let mut rng = rand::rng();
+        self.start(renderer).await;
+
         loop {
             if let Ok(result) = self.weather_receiver.try_recv() {
-                match result {
-                    Ok(weather) => {
-                        let rain_intensity = weather.condition.rain_intensity();
-                        let snow_intensity = weather.condition.snow_intensity();
-                        let fog_intensity = weather.condition.fog_intensity();
-                        let wind_speed = weather.wind_speed;
-                        let wind_direction = weather.wind_direction;
-
-                        self.state.update_weather(weather);
-                        self.animations.update_rain_intensity(rain_intensity);
-                        self.animations.update_snow_intensity(snow_intensity);
-                        self.animations.update_fog_intensity(fog_intensity);
-                        self.animations
-                            .update_wind(wind_speed as f32, wind_direction as f32);
-                    }
-                    Err(_error) => {
-                        if self.state.current_weather.is_none() {
-                            let offline_weather = generate_offline_weather(&mut rng);
-                            let rain_intensity = offline_weather.condition.rain_intensity();
-                            let snow_intensity = offline_weather.condition.snow_intensity();
-                            let fog_intensity = offline_weather.condition.fog_intensity();
-                            let wind_speed = offline_weather.wind_speed;
-                            let wind_direction = offline_weather.wind_direction;
-
-                            self.state.update_weather(offline_weather);
-                            self.state.set_offline_mode(true);
-                            self.animations.update_rain_intensity(rain_intensity);
-                            self.animations.update_snow_intensity(snow_intensity);
-                            self.animations.update_fog_intensity(fog_intensity);
-                            self.animations
-                                .update_wind(wind_speed as f32, wind_direction as f32);
-                        } else {
-                            self.state.set_offline_mode(true);
-                        }
-                    }
-                }
-            }
-
-            renderer.clear()?;
-
-            let (term_width, term_height) = renderer.get_size();
-
-            self.animations.render_background(
-                renderer,
-                &self.state.weather_conditions,
-                &self.state,
-                term_width,
-                term_height,
-                &mut rng,
-            )?;
-
-            self.scene
-                .render(renderer, &self.state.weather_conditions)?;
-
-            self.animations.render_chimney_smoke(
-                renderer,
-                &self.state.weather_conditions,
-                term_width,
-                term_height,
-                &mut rng,
-            )?;
-
-            self.animations.render_foreground(
-                renderer,
-                &self.state.weather_conditions,
-                term_width,
-                term_height,
-                &mut rng,
-            )?;
-
-            self.state.update_loading_animation();
-            self.state.update_cached_info();
-
-            // Only render HUD and attribution in normal mode (not background)
-            if !self.background_mode {
-                if !self.hide_hud {
-                    renderer.render_line_colored(
-                        2,
-                        1,
-                        &self.state.cached_weather_info,
-                        crossterm::style::Color::Cyan,
-                    )?;
-                }
-
-                let attribution = format!("Weather data by {}", self.provider_name);
-                let attribution_x = if term_width > attribution.len() as u16 {
-                    term_width - attribution.len() as u16 - 2
-                } else {
-                    0
-                };
-                let attribution_y = if term_height > 0 { term_height - 1 } else { 0 };
-                renderer.render_line_colored(
-                    attribution_x,
-                    attribution_y,
-                    &attribution,
-                    crossterm::style::Color::DarkGrey,
-                )?;
+                self.apply_weather(result).await;
             }
 
-            // In background mode, render weather info at the bottom (behind shell)
-            if self.background_mode {
-                // Get the full weather info and remove the "Press 'q' to quit" part
-                let weather_info = self.state.cached_weather_info
-                    .replace(" | Press 'q' to quit", "")
-                    .replace("Press 'q' to quit", "");
-
-                if !weather_info.is_empty() {
-                    let info_x = 2; // Left side of screen
-                    let info_y = if term_height > 0 { term_height - 1 } else { 0 };
-                    renderer.render_line_colored(
-                        info_x,
-                        info_y,
-                        &weather_info,
-                        crossterm::style::Color::Cyan,
-                    )?;
-                }
-            }
+            self.draw(renderer)?;
 
             // Render shell overlay if in background mode
             if let Some(ref mut shell) = self.shell_manager {
@@ -396,7 +351,7 @@ impl App {
                             let shell_height = height.saturating_sub(1);
                             // Ignore resize errors if shell has exited
                             if let Err(e) = shell.resize(width, shell_height) {
-                                let io_err = io::Error::new(io::ErrorKind::Other, e.to_string());
+                                let io_err = io::Error::other(e.to_string());
                                 // If shell has exited, continue without resizing
                                 if io_err.raw_os_error() != Some(5)
                                     && io_err.kind() != io::ErrorKind::BrokenPipe {
@@ -420,16 +375,275 @@ impl App {
                 }
             }
 
-            let (term_width, term_height) = renderer.get_size();
-            self.scene.update_size(term_width, term_height);
+            self.advance(renderer);
+        }
+
+        Ok(())
+    }
 
-            self.animations
-                .update_sunny_animation(&self.state.weather_conditions);
+    /// Plays the scene off-screen for `duration` at the live frame rate and
+    /// hands every frame to `recorder`. Live weather is waited for first, so
+    /// the recording does not open on the loading screen.
+    pub async fn record<W: Write>(
+        &mut self,
+        renderer: &mut TerminalRenderer,
+        duration: Duration,
+        recorder: &mut Recorder<W>,
+    ) -> io::Result<()> {
+        self.start_offscreen(renderer).await;
+        // Settle the effects on the opening scene, then let weather that
+        // arrives later cross-fade on camera as it does live
+        self.draw(renderer)?;
+        self.animations.set_instant_fades(false);
+
+        // Paced in real time, since fades and the sun's animation follow the clock
+        let mut ticks = tokio::time::interval(FRAME_DURATION);
+        let frames = duration.as_millis().div_ceil(FRAME_DURATION.as_millis()) as u32;
+        for frame in 0..frames {
+            ticks.tick().await;
+            if let Ok(result) = self.weather_receiver.try_recv() {
+                self.apply_weather(result).await;
+            }
+
+            self.draw(renderer)?;
+            renderer.flush()?;
+            recorder.capture(renderer, FRAME_DURATION * frame)?;
+            self.advance(renderer);
         }
 
         Ok(())
     }
 
+    /// Draws a single frame off-screen, once live weather is in and the
+    /// animations have run long enough to look like a scene in progress.
+    pub async fn snapshot(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        self.start_offscreen(renderer).await;
+        for _ in 0..SNAPSHOT_WARM_UP_FRAMES {
+            self.draw(renderer)?;
+            self.advance(renderer);
+        }
+        self.draw(renderer)
+    }
+
+    /// Prunes and loads history before the first frame.
+    async fn start(&mut self, renderer: &TerminalRenderer) {
+        self.refresh_season();
+        self.state.utf8 = renderer.supports_utf8();
+        if let Some(ref history) = self.history {
+            if let Err(e) = history.prune(self.history_retention_secs).await {
+                self.state.set_storage_error(Some(e.to_string()));
+            }
+            let records = self.reload_history().await;
+            self.scene.seed_from_history(&records);
+        }
+    }
+
+    /// Like `start`, then waits for the first live weather so that a file does
+    /// not show the loading screen. Effects skip their fades, so the scene is
+    /// not half faded in on the first frame.
+    async fn start_offscreen(&mut self, renderer: &TerminalRenderer) {
+        self.offscreen = true;
+        self.animations.set_instant_fades(true);
+        self.start(renderer).await;
+        if self.state.current_weather.is_none() {
+            if let Ok(Some(result)) =
+                tokio::time::timeout(OFFSCREEN_WEATHER_WAIT, self.weather_receiver.recv()).await
+            {
+                self.apply_weather(result).await;
+            }
+        }
+    }
+
+    async fn apply_weather(&mut self, result: Result<WeatherData, WeatherError>) {
+        match result {
+            Ok(weather) => {
+                self.animations.update_weather(&weather);
+                self.state.update_weather(weather);
+                if let Some(ref client) = self.weather_client {
+                    self.state.set_storage_error(client.storage_error().await);
+                }
+                self.reload_history().await;
+                self.refresh_season();
+            }
+            Err(_error) => {
+                if self.state.current_weather.is_none() {
+                    self.load_offline_weather().await;
+                } else {
+                    self.state.set_offline_mode(true);
+                }
+            }
+        }
+    }
+
+    /// Draws the scene, animations and HUD into the renderer's buffer.
+    fn draw(&mut self, renderer: &mut TerminalRenderer) -> io::Result<()> {
+        renderer.clear()?;
+
+        let (term_width, term_height) = renderer.get_size();
+
+        if self.sky_fill {
+            // A replay frame only says whether it is day, so its sky skips dawn and dusk
+            let hour = (!self.is_replay).then(|| {
+                self.simulated_time
+                    .unwrap_or_else(|| Local::now().time())
+                    .hour()
+            });
+            self.scene
+                .render_sky(renderer, &self.state.weather_conditions, hour)?;
+        }
+
+        self.animations.render_background(
+            renderer,
+            &self.state.weather_conditions,
+            &self.state,
+            term_width,
+            term_height,
+            &mut self.rng,
+        )?;
+
+        self.scene.update(self.state.current_weather.as_ref());
+        self.animations.set_ground_wetness(self.scene.wetness());
+        self.scene
+            .render(renderer, &self.state.weather_conditions)?;
+
+        self.animations.render_chimney_smoke(
+            renderer,
+            &self.state,
+            term_width,
+            term_height,
+            &mut self.rng,
+        )?;
+
+        self.animations.render_icicles(
+            renderer,
+            &self.state.weather_conditions,
+            term_width,
+            term_height,
+            &mut self.rng,
+        )?;
+
+        self.animations.render_foreground(
+            renderer,
+            &self.state.weather_conditions,
+            term_width,
+            term_height,
+            &mut self.rng,
+        )?;
+
+        self.state.update_loading_animation();
+        self.state.update_cached_info();
+
+        let hud_colors = renderer.palette(self.state.weather_conditions.is_day).hud;
+
+        // Only render HUD and attribution in normal mode (not background)
+        if !self.background_mode {
+            if !self.hide_hud {
+                let weather_info = if self.offscreen {
+                    Cow::Owned(without_quit_hint(&self.state.cached_weather_info))
+                } else {
+                    Cow::Borrowed(&self.state.cached_weather_info)
+                };
+                renderer.render_line_colored(2, 1, &weather_info, hud_colors.text)?;
+
+                for (i, line) in self.state.history_lines.iter().enumerate() {
+                    renderer.render_line_colored(
+                        2,
+                        2 + i as u16,
+                        line,
+                        hud_colors.history,
+                    )?;
+                }
+            }
+
+            let attribution = format!("Weather data by {}", self.provider_name);
+            let attribution_width = display_width(&attribution) as u16;
+            let attribution_x = if term_width > attribution_width {
+                term_width - attribution_width - 2
+            } else {
+                0
+            };
+            let attribution_y = if term_height > 0 { term_height - 1 } else { 0 };
+            renderer.render_line_colored(
+                attribution_x,
+                attribution_y,
+                &attribution,
+                hud_colors.attribution,
+            )?;
+        }
+
+        // In background mode, render weather info at the bottom (behind shell)
+        if self.background_mode {
+            let weather_info = without_quit_hint(&self.state.cached_weather_info);
+
+            if !weather_info.is_empty() {
+                let info_x = 2; // Left side of screen
+                let info_y = if term_height > 0 { term_height - 1 } else { 0 };
+                renderer.render_line_colored(
+                    info_x,
+                    info_y,
+                    &weather_info,
+                    hud_colors.text,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves on to the next frame after it has been shown.
+    fn advance(&mut self, renderer: &TerminalRenderer) {
+        let (term_width, term_height) = renderer.get_size();
+        self.scene.update_size(term_width, term_height);
+
+        self.animations.update_sunny_animation();
+    }
+
+    /// Re-checks the season, which changes rarely enough to follow weather updates.
+    fn refresh_season(&mut self) {
+        let season = self
+            .season_override
+            .unwrap_or_else(|| Season::current(self.state.location.latitude));
+        self.scene.set_season(season);
+        self.animations.set_season(season);
+    }
+
+    async fn reload_history(&mut self) -> Vec<HistoryRecord> {
+        let Some(ref history) = self.history else {
+            return Vec::new();
+        };
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let records = history
+            .load_since(now.saturating_sub(history::HISTORY_WINDOW_SECS))
+            .await;
+        self.state.set_history(&records, now);
+        records
+    }
+
+    /// Falls back to the last cached weather regardless of age, and only invents
+    /// random conditions when nothing has ever been cached for this location.
+    async fn load_offline_weather(&mut self) {
+        let stale = match self.cache_key {
+            Some(ref key) => cache::load_stale_weather(key).await,
+            None => None,
+        };
+
+        match stale {
+            Some((weather, cached_at)) => {
+                let cached_at = Local
+                    .timestamp_opt(cached_at as i64, 0)
+                    .single()
+                    .unwrap_or_else(Local::now);
+                self.animations.update_weather(&weather);
+                self.state.update_stale_weather(weather, cached_at);
+            }
+            None => {
+                let weather = generate_offline_weather(&mut self.rng);
+                self.animations.update_weather(&weather);
+                self.state.update_offline_weather(weather);
+            }
+        }
+    }
+
     /// Handles input when in background shell mode
     /// Returns Ok(true) if the application should exit, Ok(false) otherwise
     fn handle_background_input(&mut self, key: KeyEvent) -> io::Result<bool> {
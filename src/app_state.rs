@@ -1,9 +1,18 @@
+use crate::history::{self, HistoryRecord};
 use crate::weather::{
     WeatherCondition, WeatherConditions, WeatherData, WeatherLocation, WeatherUnits,
     format_precipitation, format_temperature, format_wind_speed,
 };
+use chrono::{DateTime, Local};
 use std::time::Instant;
 
+/// Cloud cover (percent) at which the sun is hidden even if the condition is not overcast.
+const SUN_HIDDEN_CLOUD_COVER: f64 = 85.0;
+/// Temperature (°C) below which the house is heated and the chimney smokes.
+const HEATING_TEMPERATURE: f64 = 12.0;
+/// Temperature (°C) from which the air shimmers above the ground on sunny days.
+const HEAT_SHIMMER_TEMPERATURE: f64 = 30.0;
+
 pub struct AppState {
     pub current_weather: Option<WeatherData>,
     pub is_offline: bool,
@@ -14,6 +23,14 @@ pub struct AppState {
     pub location: WeatherLocation,
     pub hide_location: bool,
     pub units: WeatherUnits,
+    /// When the displayed data was fetched; `None` for generated offline data.
+    pub data_fetched_at: Option<DateTime<Local>>,
+    rendered_age_minutes: Option<i64>,
+    pub storage_error: Option<String>,
+    /// Sparkline trend panel built from recorded history; empty when disabled.
+    pub history_lines: Vec<String>,
+    /// Whether the terminal takes UTF-8, so sparklines can use block characters.
+    pub utf8: bool,
 }
 
 impl AppState {
@@ -28,6 +45,11 @@ impl AppState {
             location,
             hide_location,
             units,
+            data_fetched_at: None,
+            rendered_age_minutes: None,
+            storage_error: None,
+            history_lines: Vec::new(),
+            utf8: true,
         }
     }
 
@@ -38,18 +60,55 @@ impl AppState {
             weather.condition.is_raining() && !self.weather_conditions.is_thunderstorm;
         self.weather_conditions.is_cloudy = weather.condition.is_cloudy();
         self.weather_conditions.is_foggy = weather.condition.is_foggy();
+        self.weather_conditions.is_hail = weather.condition.is_hail();
+        self.weather_conditions.is_freezing_rain = weather.condition.is_freezing_rain();
+        self.weather_conditions.is_sleet = weather.is_sleet();
         self.weather_conditions.is_day = weather.is_day;
 
         self.current_weather = Some(weather);
         self.is_offline = false;
+        self.data_fetched_at = Some(Local::now());
         self.weather_info_needs_update = true;
     }
 
+    /// Shows previously cached data while offline, marked stale since `cached_at`.
+    pub fn update_stale_weather(&mut self, weather: WeatherData, cached_at: DateTime<Local>) {
+        self.update_weather(weather);
+        self.data_fetched_at = Some(cached_at);
+        self.is_offline = true;
+    }
+
+    /// Shows generated placeholder data when there is nothing cached to fall back on.
+    pub fn update_offline_weather(&mut self, weather: WeatherData) {
+        self.update_weather(weather);
+        self.data_fetched_at = None;
+        self.is_offline = true;
+    }
+
+    fn stale_age_minutes(&self, now: DateTime<Local>) -> Option<i64> {
+        if !self.is_offline {
+            return None;
+        }
+        self.data_fetched_at
+            .map(|fetched_at| (now - fetched_at).num_minutes().max(0))
+    }
+
     pub fn set_offline_mode(&mut self, offline: bool) {
         self.is_offline = offline;
         self.weather_info_needs_update = true;
     }
 
+    pub fn set_storage_error(&mut self, error: Option<String>) {
+        if self.storage_error != error {
+            self.storage_error = error;
+            self.weather_info_needs_update = true;
+        }
+    }
+
+    pub fn set_history(&mut self, records: &[HistoryRecord], now: u64) {
+        self.history_lines = history::panel_lines(records, now, &self.units, self.utf8);
+    }
+
     pub fn update_loading_animation(&mut self) {
         if self.loading_state.should_update() {
             self.loading_state.next_frame();
@@ -81,9 +140,16 @@ impl AppState {
     }
 
     pub fn update_cached_info(&mut self) {
-        if !self.weather_info_needs_update {
+        self.update_cached_info_at(Local::now());
+    }
+
+    /// Rebuilds the info line as of `now`, which dates the stale marker.
+    fn update_cached_info_at(&mut self, now: DateTime<Local>) {
+        let age_minutes = self.stale_age_minutes(now);
+        if !self.weather_info_needs_update && age_minutes == self.rendered_age_minutes {
             return;
         }
+        self.rendered_age_minutes = age_minutes;
 
         let location_str = if self.hide_location {
             String::new()
@@ -110,11 +176,28 @@ impl AppState {
             let (precip, precip_unit) =
                 format_precipitation(weather.precipitation, self.units.precipitation);
 
-            let offline_indicator = if self.is_offline { "OFFLINE | " } else { "" };
+            let offline_indicator = match (age_minutes, self.data_fetched_at) {
+                (Some(minutes), Some(fetched_at)) => {
+                    let since = if fetched_at.date_naive() == now.date_naive() {
+                        fetched_at.format("%H:%M").to_string()
+                    } else {
+                        fetched_at.format("%b %d %H:%M").to_string()
+                    };
+                    format!("STALE since {} ({}) | ", since, format_data_age(minutes))
+                }
+                _ if self.is_offline => "OFFLINE | ".to_string(),
+                _ => String::new(),
+            };
+            let storage_indicator = if self.storage_error.is_some() {
+                "DISK WRITE FAILED | "
+            } else {
+                ""
+            };
 
             format!(
-                "{}Weather: {} | Temp: {:.1}{} | Wind: {:.1}{} | Precip: {:.1}{}{} | Press 'q' to quit",
+                "{}{}Weather: {} | Temp: {:.1}{} | Wind: {:.1}{} | Precip: {:.1}{}{} | Press 'q' to quit",
                 offline_indicator,
+                storage_indicator,
                 self.get_condition_text(),
                 temp,
                 temp_unit,
@@ -140,7 +223,7 @@ impl AppState {
             matches!(
                 weather.condition,
                 WeatherCondition::Clear | WeatherCondition::PartlyCloudy | WeatherCondition::Cloudy
-            )
+            ) && weather.cloud_cover < SUN_HIDDEN_CLOUD_COVER
         } else {
             false
         }
@@ -166,6 +249,46 @@ impl AppState {
             false
         }
     }
+
+    /// The fire is only lit when it is cold enough to heat the house, and rain
+    /// would beat the smoke down anyway.
+    pub fn should_show_chimney_smoke(&self) -> bool {
+        if self.weather_conditions.is_raining || self.weather_conditions.is_thunderstorm {
+            return false;
+        }
+
+        self.current_weather
+            .as_ref()
+            .is_some_and(|weather| weather.temperature < HEATING_TEMPERATURE)
+    }
+
+    pub fn should_show_heat_shimmer(&self) -> bool {
+        if !self.weather_conditions.is_day {
+            return false;
+        }
+
+        if let Some(ref weather) = self.current_weather {
+            weather.temperature >= HEAT_SHIMMER_TEMPERATURE
+                && matches!(
+                    weather.condition,
+                    WeatherCondition::Clear | WeatherCondition::PartlyCloudy
+                )
+        } else {
+            false
+        }
+    }
+}
+
+pub fn format_data_age(minutes: i64) -> String {
+    if minutes < 1 {
+        "just now".to_string()
+    } else if minutes < 60 {
+        format!("{}m ago", minutes)
+    } else if minutes < 60 * 24 {
+        format!("{}h {}m ago", minutes / 60, minutes % 60)
+    } else {
+        format!("{}d ago", minutes / (60 * 24))
+    }
 }
 
 pub struct LoadingState {
@@ -201,6 +324,7 @@ impl LoadingState {
 mod tests {
     use super::*;
     use crate::weather::types::{PrecipitationUnit, TemperatureUnit, WindSpeedUnit};
+    use chrono::TimeZone;
 
     fn create_app_state(lat: f64, lon: f64) -> AppState {
         let location = WeatherLocation {
@@ -229,12 +353,157 @@ mod tests {
             is_day: true,
             moon_phase: Some(0.5),
             timestamp: "2024-01-01T12:00:00Z".to_string(),
+            precipitation_level: None,
         };
         app.update_weather(weather);
 
         app
     }
 
+    fn sample_weather() -> WeatherData {
+        create_app_state(0.0, 0.0).current_weather.unwrap()
+    }
+
+    #[test]
+    fn test_stale_weather_shows_since_and_age() {
+        let now = Local.with_ymd_and_hms(2024, 1, 10, 14, 30, 0).unwrap();
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_stale_weather(sample_weather(), now - chrono::Duration::minutes(125));
+        app.update_cached_info_at(now);
+
+        assert!(
+            app.cached_weather_info
+                .starts_with("STALE since 12:25 (2h 5m ago) | ")
+        );
+        assert!(!app.cached_weather_info.contains("OFFLINE"));
+
+        // Data from before midnight is dated
+        let now = Local.with_ymd_and_hms(2024, 1, 10, 0, 20, 0).unwrap();
+        app.update_stale_weather(sample_weather(), now - chrono::Duration::minutes(30));
+        app.update_cached_info_at(now);
+        assert!(
+            app.cached_weather_info
+                .starts_with("STALE since Jan 09 23:50 (30m ago) | ")
+        );
+    }
+
+    #[test]
+    fn test_generated_offline_weather_has_no_age() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_offline_weather(sample_weather());
+        app.update_cached_info();
+
+        assert!(app.cached_weather_info.starts_with("OFFLINE | "));
+        assert!(!app.cached_weather_info.contains("STALE"));
+    }
+
+    #[test]
+    fn test_live_weather_clears_stale_marker() {
+        let mut app = create_app_state(0.0, 0.0);
+        app.update_stale_weather(sample_weather(), Local::now());
+        app.update_weather(sample_weather());
+        app.update_cached_info();
+
+        assert!(!app.cached_weather_info.contains("STALE"));
+        assert!(!app.cached_weather_info.contains("OFFLINE"));
+    }
+
+    #[test]
+    fn test_sun_hidden_by_heavy_cloud_cover() {
+        let mut app = create_app_state(0.0, 0.0);
+        assert!(app.should_show_sun());
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::Cloudy,
+            cloud_cover: 60.0,
+            ..sample_weather()
+        });
+        assert!(app.should_show_sun());
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::Cloudy,
+            cloud_cover: 95.0,
+            ..sample_weather()
+        });
+        assert!(!app.should_show_sun());
+    }
+
+    #[test]
+    fn test_icy_precipitation_conditions() {
+        let mut app = create_app_state(0.0, 0.0);
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::ThunderstormHail,
+            ..sample_weather()
+        });
+        assert!(app.weather_conditions.is_hail);
+        assert!(!app.weather_conditions.is_sleet);
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::FreezingRain,
+            temperature: 0.0,
+            ..sample_weather()
+        });
+        assert!(app.weather_conditions.is_freezing_rain);
+        assert!(!app.weather_conditions.is_sleet);
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::Rain,
+            temperature: 1.0,
+            ..sample_weather()
+        });
+        assert!(app.weather_conditions.is_sleet);
+        assert!(!app.weather_conditions.is_hail);
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::Rain,
+            temperature: 8.0,
+            ..sample_weather()
+        });
+        assert!(!app.weather_conditions.is_sleet);
+    }
+
+    #[test]
+    fn test_temperature_driven_effects() {
+        let mut app = create_app_state(0.0, 0.0);
+        assert!(!app.should_show_chimney_smoke());
+        assert!(!app.should_show_heat_shimmer());
+
+        app.update_weather(WeatherData {
+            temperature: 3.0,
+            ..sample_weather()
+        });
+        assert!(app.should_show_chimney_smoke());
+
+        app.update_weather(WeatherData {
+            condition: WeatherCondition::Rain,
+            temperature: 3.0,
+            ..sample_weather()
+        });
+        assert!(!app.should_show_chimney_smoke());
+
+        app.update_weather(WeatherData {
+            temperature: 34.0,
+            ..sample_weather()
+        });
+        assert!(app.should_show_heat_shimmer());
+
+        app.update_weather(WeatherData {
+            temperature: 34.0,
+            is_day: false,
+            ..sample_weather()
+        });
+        assert!(!app.should_show_heat_shimmer());
+    }
+
+    #[test]
+    fn test_format_data_age() {
+        assert_eq!(format_data_age(0), "just now");
+        assert_eq!(format_data_age(42), "42m ago");
+        assert_eq!(format_data_age(61), "1h 1m ago");
+        assert_eq!(format_data_age(60 * 24 * 3 + 5), "3d ago");
+    }
+
     #[test]
     fn test_new_york_coordinates() {
         // New York: 40.7128°N, 74.0060°W (positive lat, negative lon)
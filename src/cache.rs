@@ -1,26 +1,107 @@
+use crate::error::CacheError;
 use crate::geolocation::GeoLocation;
-use crate::weather::WeatherData;
+use crate::weather::{WeatherData, WeatherUnits};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::fs;
 
+/// Bump whenever the on-disk layout of a cache entry changes.
+const CACHE_SCHEMA_VERSION: u32 = 2;
+
 const LOCATION_CACHE_DURATION_SECS: u64 = 86400;
 const WEATHER_CACHE_DURATION_SECS: u64 = 300;
+const WEATHER_CACHE_MAX_AGE_SECS: u64 = 7 * 86400;
+const WEATHER_CACHE_MAX_BYTES: u64 = 256 * 1024;
+const WEATHER_CACHE_DIR: &str = "weather";
+/// How long a temp file may sit before it counts as left by a crashed write.
+const TEMP_FILE_MAX_AGE: Duration = Duration::from_secs(10 * 60);
 
 #[derive(Serialize, Deserialize)]
 struct LocationCache {
+    #[serde(default)]
+    schema_version: u32,
     location: GeoLocation,
     cached_at: u64,
 }
 
 #[derive(Serialize, Deserialize)]
 struct WeatherCache {
+    #[serde(default)]
+    schema_version: u32,
     data: WeatherData,
     cached_at: u64,
     location_key: String,
+    provider: String,
+}
+
+/// Read before the full entry so files from other schema versions are skipped
+/// instead of being misparsed.
+#[derive(Deserialize)]
+struct SchemaHeader {
+    #[serde(default)]
+    schema_version: u32,
+}
+
+/// Identifies one weather cache entry: a location, a provider and a set of units.
+#[derive(Debug, Clone)]
+pub struct WeatherCacheKey {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub provider: String,
+    pub units: WeatherUnits,
 }
 
-fn get_cache_dir() -> Option<PathBuf> {
+impl WeatherCacheKey {
+    pub fn new(latitude: f64, longitude: f64, provider: &str, units: WeatherUnits) -> Self {
+        Self {
+            latitude,
+            longitude,
+            provider: provider.to_string(),
+            units,
+        }
+    }
+
+    fn location_key(&self) -> String {
+        make_location_key(self.latitude, self.longitude)
+    }
+
+    fn file_name(&self) -> String {
+        let units = format!(
+            "{:?}-{:?}-{:?}",
+            self.units.temperature, self.units.wind_speed, self.units.precipitation
+        )
+        .to_lowercase();
+        format!(
+            "{}_{}_{}.json",
+            self.location_key().replace(',', "_"),
+            self.provider,
+            units
+        )
+    }
+}
+
+/// A location cache entry as found on disk, for `weathr cache show`.
+#[derive(Debug, Clone)]
+pub struct LocationCacheInfo {
+    pub path: PathBuf,
+    pub location: GeoLocation,
+    pub age_secs: u64,
+    pub expired: bool,
+}
+
+/// A weather cache entry as found on disk, for `weathr cache show`.
+#[derive(Debug, Clone)]
+pub struct WeatherCacheInfo {
+    pub path: PathBuf,
+    pub location_key: String,
+    pub provider: String,
+    pub data: WeatherData,
+    pub age_secs: u64,
+}
+
+pub fn get_cache_dir() -> Option<PathBuf> {
     let cache_dir = if let Ok(xdg_cache) = std::env::var("XDG_CACHE_HOME") {
         PathBuf::from(xdg_cache)
     } else {
@@ -29,9 +110,17 @@ fn get_cache_dir() -> Option<PathBuf> {
     Some(cache_dir.join("weathr"))
 }
 
+pub fn location_cache_path() -> Option<PathBuf> {
+    Some(get_cache_dir()?.join("location.json"))
+}
+
+pub fn weather_cache_dir() -> Option<PathBuf> {
+    Some(get_cache_dir()?.join(WEATHER_CACHE_DIR))
+}
+
 fn current_timestamp() -> u64 {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
         .map(|d| d.as_secs())
         .unwrap_or(0)
 }
@@ -40,70 +129,552 @@ fn make_location_key(latitude: f64, longitude: f64) -> String {
     format!("{:.2},{:.2}", latitude, longitude)
 }
 
+fn has_current_schema(contents: &str) -> bool {
+    serde_json::from_str::<SchemaHeader>(contents)
+        .is_ok_and(|header| header.schema_version == CACHE_SCHEMA_VERSION)
+}
+
+/// Whether `path` is the `<name>.tmp.<pid>` file of an atomic write.
+fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.contains(".tmp."))
+}
+
+/// Writes to a temporary file first and renames it into place, so readers never
+/// see a partially written file. Temp files an earlier crashed write left for
+/// the same path are swept on the way.
+pub(crate) async fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    sweep_temp_files(path, SystemTime::now()).await;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(format!(".tmp.{}", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    fs::write(&tmp_path, contents).await?;
+    if let Err(e) = fs::rename(&tmp_path, path).await {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Best-effort removal of stale `<name>.tmp.<pid>` files next to `path`.
+async fn sweep_temp_files(path: &Path, now: SystemTime) {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return;
+    };
+    let prefix = format!("{}.tmp.", name);
+    let Ok(mut read_dir) = fs::read_dir(dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = read_dir.next_entry().await {
+        if !entry
+            .file_name()
+            .to_str()
+            .is_some_and(|entry_name| entry_name.starts_with(&prefix))
+        {
+            continue;
+        }
+        if let Ok(metadata) = entry.metadata().await
+            && is_stale_temp_file(metadata.modified().unwrap_or(UNIX_EPOCH), now)
+        {
+            let _ = fs::remove_file(entry.path()).await;
+        }
+    }
+}
+
+/// Whether a temp file last written at `modified` was left by a crashed write
+/// rather than one still in progress.
+fn is_stale_temp_file(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified).unwrap_or_default() > TEMP_FILE_MAX_AGE
+}
+
+async fn write_entry(path: &Path, contents: &[u8]) -> Result<(), CacheError> {
+    write_atomic(path, contents)
+        .await
+        .map_err(|source| CacheError::WriteError {
+            path: path.display().to_string(),
+            source,
+        })
+}
+
 pub async fn load_cached_location() -> Option<GeoLocation> {
-    let cache_path = get_cache_dir()?.join("location.json");
+    let cache_path = location_cache_path()?;
     let contents = fs::read_to_string(&cache_path).await.ok()?;
+    if !has_current_schema(&contents) {
+        return None;
+    }
     let cache: LocationCache = serde_json::from_str(&contents).ok()?;
 
     let now = current_timestamp();
-    if now - cache.cached_at < LOCATION_CACHE_DURATION_SECS {
+    if now.saturating_sub(cache.cached_at) < LOCATION_CACHE_DURATION_SECS {
         Some(cache.location)
     } else {
         None
     }
 }
 
-pub fn save_location_cache(location: &GeoLocation) {
-    let location = location.clone();
-    tokio::spawn(async move {
-        if let Some(cache_dir) = get_cache_dir() {
-            let _ = fs::create_dir_all(&cache_dir).await;
+pub async fn save_location_cache(location: &GeoLocation) -> Result<(), CacheError> {
+    let cache_dir = get_cache_dir().ok_or(CacheError::NoCacheDir)?;
 
-            let cache = LocationCache {
-                location,
-                cached_at: current_timestamp(),
-            };
+    let cache = LocationCache {
+        schema_version: CACHE_SCHEMA_VERSION,
+        location: location.clone(),
+        cached_at: current_timestamp(),
+    };
 
-            if let Ok(json) = serde_json::to_string(&cache) {
-                let _ = fs::write(cache_dir.join("location.json"), json).await;
-            }
-        }
-    });
+    let json = serde_json::to_string(&cache)?;
+    write_entry(&cache_dir.join("location.json"), json.as_bytes()).await
 }
 
-pub async fn load_cached_weather(latitude: f64, longitude: f64) -> Option<WeatherData> {
-    let cache_path = get_cache_dir()?.join("weather.json");
-    let contents = fs::read_to_string(&cache_path).await.ok()?;
+/// Reads the location cache regardless of age.
+pub async fn inspect_location_cache() -> Option<LocationCacheInfo> {
+    let path = location_cache_path()?;
+    let contents = fs::read_to_string(&path).await.ok()?;
+    if !has_current_schema(&contents) {
+        return None;
+    }
+    let cache: LocationCache = serde_json::from_str(&contents).ok()?;
+
+    let age_secs = current_timestamp().saturating_sub(cache.cached_at);
+    Some(LocationCacheInfo {
+        path,
+        location: cache.location,
+        age_secs,
+        expired: age_secs >= LOCATION_CACHE_DURATION_SECS,
+    })
+}
+
+/// Removes the location cache so the next run re-detects the location.
+/// Returns whether there was anything to remove.
+pub async fn clear_location_cache() -> Result<bool, CacheError> {
+    let path = location_cache_path().ok_or(CacheError::NoCacheDir)?;
+    remove_if_exists(&path).await
+}
+
+async fn remove_if_exists(path: &Path) -> Result<bool, CacheError> {
+    match fs::remove_file(path).await {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(source) => Err(CacheError::RemoveError {
+            path: path.display().to_string(),
+            source,
+        }),
+    }
+}
+
+async fn read_weather_entry(dir: &Path, key: &WeatherCacheKey) -> Option<WeatherCache> {
+    let contents = fs::read_to_string(dir.join(key.file_name())).await.ok()?;
+    if !has_current_schema(&contents) {
+        return None;
+    }
     let cache: WeatherCache = serde_json::from_str(&contents).ok()?;
 
-    let location_key = make_location_key(latitude, longitude);
-    if cache.location_key != location_key {
+    // Guard against two locations rounding to the same file name
+    if cache.location_key != key.location_key() || cache.provider != key.provider {
         return None;
     }
+    Some(cache)
+}
+
+async fn write_weather_entry(
+    dir: &Path,
+    key: &WeatherCacheKey,
+    weather: &WeatherData,
+    cached_at: u64,
+) -> Result<(), CacheError> {
+    let cache = WeatherCache {
+        schema_version: CACHE_SCHEMA_VERSION,
+        data: weather.clone(),
+        cached_at,
+        location_key: key.location_key(),
+        provider: key.provider.clone(),
+    };
+
+    let json = serde_json::to_string(&cache)?;
+    write_entry(&dir.join(key.file_name()), json.as_bytes()).await
+}
+
+/// Removes entries older than `max_age`, then the oldest entries until the
+/// directory fits in `max_bytes`, along with stale temp files. Files another
+/// instance removed first count as evicted.
+async fn evict_weather_entries(
+    dir: &Path,
+    now: SystemTime,
+    max_age: Duration,
+    max_bytes: u64,
+) -> Result<(), CacheError> {
+    let evict_error = |source| CacheError::EvictError {
+        path: dir.display().to_string(),
+        source,
+    };
+
+    let mut entries = Vec::new();
+    let mut read_dir = fs::read_dir(dir).await.map_err(evict_error)?;
+    while let Some(entry) = read_dir.next_entry().await.map_err(evict_error)? {
+        let path = entry.path();
+        let is_temp = is_temp_file(&path);
+        if !is_temp && path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let metadata = match entry.metadata().await {
+            Ok(metadata) => metadata,
+            // Another instance evicted it since the listing
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(evict_error(e)),
+        };
+        let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+        if is_temp {
+            // Left behind by a write that crashed before its rename
+            if is_stale_temp_file(modified, now) {
+                remove_if_exists(&path).await?;
+            }
+            continue;
+        }
+        entries.push((path, modified, metadata.len()));
+    }
+
+    // Newest first, so the size budget is spent on the most recent entries
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+
+    let mut total_bytes = 0;
+    for (path, modified, len) in entries {
+        total_bytes += len;
+        let age = now.duration_since(modified).unwrap_or_default();
+        if age > max_age || total_bytes > max_bytes {
+            remove_if_exists(&path).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn load_cached_weather(key: &WeatherCacheKey) -> Option<WeatherData> {
+    let dir = get_cache_dir()?.join(WEATHER_CACHE_DIR);
+    let cache = read_weather_entry(&dir, key).await?;
 
     let now = current_timestamp();
-    if now - cache.cached_at < WEATHER_CACHE_DURATION_SECS {
+    if now.saturating_sub(cache.cached_at) < WEATHER_CACHE_DURATION_SECS {
         Some(cache.data)
     } else {
         None
     }
 }
 
-pub fn save_weather_cache(weather: &WeatherData, latitude: f64, longitude: f64) {
-    let weather = weather.clone();
-    tokio::spawn(async move {
-        if let Some(cache_dir) = get_cache_dir() {
-            let _ = fs::create_dir_all(&cache_dir).await;
+/// Returns the cached weather for this key regardless of age, along with the
+/// unix timestamp it was cached at. Used to show stale data when the network is down.
+pub async fn load_stale_weather(key: &WeatherCacheKey) -> Option<(WeatherData, u64)> {
+    let dir = get_cache_dir()?.join(WEATHER_CACHE_DIR);
+    let cache = read_weather_entry(&dir, key).await?;
+    Some((cache.data, cache.cached_at))
+}
+
+pub async fn save_weather_cache(
+    weather: &WeatherData,
+    key: &WeatherCacheKey,
+) -> Result<(), CacheError> {
+    let dir = get_cache_dir()
+        .ok_or(CacheError::NoCacheDir)?
+        .join(WEATHER_CACHE_DIR);
 
-            let cache = WeatherCache {
-                data: weather,
-                cached_at: current_timestamp(),
-                location_key: make_location_key(latitude, longitude),
-            };
+    write_weather_entry(&dir, key, weather, current_timestamp()).await?;
+    evict_weather_entries(
+        &dir,
+        SystemTime::now(),
+        Duration::from_secs(WEATHER_CACHE_MAX_AGE_SECS),
+        WEATHER_CACHE_MAX_BYTES,
+    )
+    .await
+}
 
-            if let Ok(json) = serde_json::to_string(&cache) {
-                let _ = fs::write(cache_dir.join("weather.json"), json).await;
-            }
+async fn list_weather_entries(dir: &Path) -> Vec<WeatherCacheInfo> {
+    let mut entries = Vec::new();
+    let Ok(mut read_dir) = fs::read_dir(dir).await else {
+        return entries;
+    };
+
+    let now = current_timestamp();
+    while let Ok(Some(entry)) = read_dir.next_entry().await {
+        let path = entry.path();
+        let Ok(contents) = fs::read_to_string(&path).await else {
+            continue;
+        };
+        if !has_current_schema(&contents) {
+            continue;
+        }
+        let Ok(cache) = serde_json::from_str::<WeatherCache>(&contents) else {
+            continue;
+        };
+        entries.push(WeatherCacheInfo {
+            path,
+            location_key: cache.location_key,
+            provider: cache.provider,
+            data: cache.data,
+            age_secs: now.saturating_sub(cache.cached_at),
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.age_secs);
+    entries
+}
+
+async fn remove_weather_entries(dir: &Path) -> Result<usize, CacheError> {
+    let remove_error = |source| CacheError::RemoveError {
+        path: dir.display().to_string(),
+        source,
+    };
+
+    let mut read_dir = match fs::read_dir(dir).await {
+        Ok(read_dir) => read_dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(remove_error(e)),
+    };
+
+    let mut removed = 0;
+    while let Some(entry) = read_dir.next_entry().await.map_err(remove_error)? {
+        if remove_if_exists(&entry.path()).await? {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Lists readable weather cache entries, most recent first.
+pub async fn inspect_weather_cache() -> Vec<WeatherCacheInfo> {
+    match weather_cache_dir() {
+        Some(dir) => list_weather_entries(&dir).await,
+        None => Vec::new(),
+    }
+}
+
+/// Removes every weather cache entry, including the pre-versioning `weather.json`.
+/// Returns the number of files removed.
+pub async fn clear_weather_cache() -> Result<usize, CacheError> {
+    let cache_dir = get_cache_dir().ok_or(CacheError::NoCacheDir)?;
+    let mut removed = remove_weather_entries(&cache_dir.join(WEATHER_CACHE_DIR)).await?;
+    if remove_if_exists(&cache_dir.join("weather.json")).await? {
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("weathr_cache_test_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn sample_weather() -> WeatherData {
+        WeatherData {
+            condition: WeatherCondition::Rain,
+            temperature: 12.0,
+            apparent_temperature: 10.0,
+            humidity: 80.0,
+            precipitation: 1.5,
+            wind_speed: 4.0,
+            wind_direction: 180.0,
+            cloud_cover: 90.0,
+            pressure: 1005.0,
+            visibility: Some(8000.0),
+            is_day: true,
+            moon_phase: Some(0.5),
+            timestamp: "2024-01-01T12:00".to_string(),
+            precipitation_level: None,
+        }
+    }
+
+    fn berlin(provider: &str) -> WeatherCacheKey {
+        WeatherCacheKey::new(52.52, 13.41, provider, WeatherUnits::default())
+    }
+
+    #[tokio::test]
+    async fn test_entries_are_keyed_per_location_and_provider() {
+        let dir = test_dir("keyed");
+        let tokyo = WeatherCacheKey::new(35.68, 139.65, "open_meteo", WeatherUnits::default());
+
+        write_weather_entry(&dir, &berlin("open_meteo"), &sample_weather(), 100)
+            .await
+            .unwrap();
+        write_weather_entry(&dir, &tokyo, &sample_weather(), 200)
+            .await
+            .unwrap();
+
+        let entry = read_weather_entry(&dir, &berlin("open_meteo"))
+            .await
+            .unwrap();
+        assert_eq!(entry.cached_at, 100);
+        let entry = read_weather_entry(&dir, &tokyo).await.unwrap();
+        assert_eq!(entry.cached_at, 200);
+        assert!(
+            read_weather_entry(&dir, &berlin("weatherapi"))
+                .await
+                .is_none()
+        );
+
+        let imperial = WeatherCacheKey::new(52.52, 13.41, "open_meteo", WeatherUnits::imperial());
+        assert_ne!(imperial.file_name(), berlin("open_meteo").file_name());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_other_schema_versions_are_ignored() {
+        let dir = test_dir("schema");
+        std::fs::create_dir_all(&dir).unwrap();
+        let key = berlin("open_meteo");
+
+        // Pre-versioning layout: no schema_version field at all
+        let legacy = serde_json::json!({
+            "data": sample_weather(),
+            "cached_at": 100,
+            "location_key": key.location_key(),
+            "provider": key.provider,
+        });
+        std::fs::write(dir.join(key.file_name()), legacy.to_string()).unwrap();
+        assert!(read_weather_entry(&dir, &key).await.is_none());
+
+        let future = serde_json::json!({ "schema_version": CACHE_SCHEMA_VERSION + 1 });
+        std::fs::write(dir.join(key.file_name()), future.to_string()).unwrap();
+        assert!(read_weather_entry(&dir, &key).await.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_atomic_write_leaves_no_temp_files() {
+        let dir = test_dir("atomic");
+        write_weather_entry(&dir, &berlin("open_meteo"), &sample_weather(), 100)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, vec![berlin("open_meteo").file_name()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_remove_weather_entries() {
+        let dir = test_dir("list");
+        assert!(list_weather_entries(&dir).await.is_empty());
+        assert_eq!(remove_weather_entries(&dir).await.unwrap(), 0);
+
+        let now = current_timestamp();
+        write_weather_entry(&dir, &berlin("open_meteo"), &sample_weather(), now - 600)
+            .await
+            .unwrap();
+        write_weather_entry(&dir, &berlin("weatherapi"), &sample_weather(), now - 60)
+            .await
+            .unwrap();
+        std::fs::write(dir.join("garbage.json"), "not json").unwrap();
+
+        let entries = list_weather_entries(&dir).await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].provider, "weatherapi");
+        assert_eq!(entries[1].provider, "open_meteo");
+        assert_eq!(entries[1].location_key, "52.52,13.41");
+        assert!(entries[1].age_secs >= 600);
+
+        assert_eq!(remove_weather_entries(&dir).await.unwrap(), 3);
+        assert!(list_weather_entries(&dir).await.is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_eviction_by_age_and_size() {
+        let dir = test_dir("evict");
+        let now = SystemTime::now();
+        let keys: Vec<WeatherCacheKey> = (0..4)
+            .map(|i| WeatherCacheKey::new(i as f64, 0.0, "open_meteo", WeatherUnits::default()))
+            .collect();
+
+        for (i, key) in keys.iter().enumerate() {
+            write_weather_entry(&dir, key, &sample_weather(), 100)
+                .await
+                .unwrap();
+            let file = std::fs::File::options()
+                .write(true)
+                .open(dir.join(key.file_name()))
+                .unwrap();
+            file.set_modified(now - Duration::from_secs(60 * i as u64))
+                .unwrap();
+        }
+
+        // Entry 3 is too old; of the rest only two fit in the size budget
+        let entry_len = std::fs::metadata(dir.join(keys[0].file_name()))
+            .unwrap()
+            .len();
+        evict_weather_entries(&dir, now, Duration::from_secs(150), entry_len * 2)
+            .await
+            .unwrap();
+
+        assert!(read_weather_entry(&dir, &keys[0]).await.is_some());
+        assert!(read_weather_entry(&dir, &keys[1]).await.is_some());
+        assert!(read_weather_entry(&dir, &keys[2]).await.is_none());
+        assert!(read_weather_entry(&dir, &keys[3]).await.is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_eviction_sweeps_stale_temp_files() {
+        let dir = test_dir("sweep");
+        let now = SystemTime::now();
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, age) in [("a.json.tmp.41", 3600), ("b.json.tmp.42", 5)] {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(now - Duration::from_secs(age)).unwrap();
         }
-    });
+
+        evict_weather_entries(&dir, now, Duration::from_secs(3600), u64::MAX)
+            .await
+            .unwrap();
+
+        // A write still in progress keeps its temp file
+        assert!(!dir.join("a.json.tmp.41").exists());
+        assert!(dir.join("b.json.tmp.42").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_sweeps_its_own_stale_temp_files() {
+        let dir = test_dir("write_sweep");
+        let now = SystemTime::now();
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, age) in [
+            ("log.jsonl.tmp.41", 3600),
+            ("log.jsonl.tmp.42", 5),
+            ("other.json.tmp.43", 3600),
+        ] {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(now - Duration::from_secs(age)).unwrap();
+        }
+
+        write_atomic(&dir.join("log.jsonl"), b"{}\n").await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dir.join("log.jsonl")).unwrap(),
+            "{}\n"
+        );
+        assert!(!dir.join("log.jsonl.tmp.41").exists());
+        assert!(dir.join("log.jsonl.tmp.42").exists());
+        // Temp files of other paths are left to eviction
+        assert!(dir.join("other.json.tmp.43").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
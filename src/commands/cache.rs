@@ -0,0 +1,116 @@
+use crate::app_state::format_data_age;
+use crate::cache::{self, LocationCacheInfo, WeatherCacheInfo};
+use crate::error::CacheError;
+use clap::Subcommand;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheCommand {
+    /// Show cached location and weather entries
+    Show,
+
+    /// Delete cached data (both location and weather unless one is chosen)
+    Clear {
+        #[arg(long, help = "Only clear the cached location (forces re-detection)")]
+        location: bool,
+
+        #[arg(long, help = "Only clear cached weather data")]
+        weather: bool,
+    },
+
+    /// Print the cache directory
+    Path,
+}
+
+pub async fn run(command: CacheCommand) -> Result<(), CacheError> {
+    match command {
+        CacheCommand::Show => {
+            show().await;
+            Ok(())
+        }
+        CacheCommand::Clear { location, weather } => {
+            // Neither flag means clear everything
+            let clear_all = !location && !weather;
+            clear(location || clear_all, weather || clear_all).await
+        }
+        CacheCommand::Path => {
+            let dir = cache::get_cache_dir().ok_or(CacheError::NoCacheDir)?;
+            println!("{}", dir.display());
+            Ok(())
+        }
+    }
+}
+
+async fn show() {
+    match cache::get_cache_dir() {
+        Some(dir) => println!("Cache directory: {}", dir.display()),
+        None => {
+            println!("Cache directory: unavailable (check $XDG_CACHE_HOME or $HOME)");
+            return;
+        }
+    }
+
+    println!();
+    match cache::inspect_location_cache().await {
+        Some(info) => print_location(&info),
+        None => println!("Location: not cached"),
+    }
+
+    println!();
+    let entries = cache::inspect_weather_cache().await;
+    if entries.is_empty() {
+        println!("Weather: not cached");
+    } else {
+        println!("Weather: {} cached entries", entries.len());
+        for entry in &entries {
+            print_weather(entry);
+        }
+    }
+}
+
+fn print_location(info: &LocationCacheInfo) {
+    let location = &info.location;
+    let name = location.city.as_deref().unwrap_or("Unknown city");
+    println!(
+        "Location: {} ({:.4}, {:.4})",
+        name, location.latitude, location.longitude
+    );
+    println!(
+        "  Age:  {}{}",
+        format_data_age((info.age_secs / 60) as i64),
+        if info.expired {
+            " (expired, will re-detect)"
+        } else {
+            ""
+        }
+    );
+    println!("  File: {}", info.path.display());
+}
+
+fn print_weather(entry: &WeatherCacheInfo) {
+    println!(
+        "  {} via {}: {:?}, {:.1}°C, {}",
+        entry.location_key,
+        entry.provider,
+        entry.data.condition,
+        entry.data.temperature,
+        format_data_age((entry.age_secs / 60) as i64)
+    );
+    println!("    File: {}", entry.path.display());
+}
+
+async fn clear(location: bool, weather: bool) -> Result<(), CacheError> {
+    if location {
+        if cache::clear_location_cache().await? {
+            println!("Cleared cached location.");
+        } else {
+            println!("No cached location to clear.");
+        }
+    }
+
+    if weather {
+        let removed = cache::clear_weather_cache().await?;
+        println!("Cleared {} cached weather entries.", removed);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod cache;
+pub mod record;
+pub mod snapshot;
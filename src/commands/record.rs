@@ -0,0 +1,101 @@
+use crate::app::App;
+use crate::error::RecordError;
+use crate::record::{RecordFormat, Recorder};
+use crate::render::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, TerminalRenderer};
+use clap::Args;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Args, Debug, Clone)]
+pub struct RecordArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        help = "Output file: .cast (asciinema), .svg or .gif"
+    )]
+    pub out: PathBuf,
+
+    #[arg(
+        short,
+        long,
+        value_name = "SECS",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..=600),
+        help = "Length of the recording in seconds"
+    )]
+    pub duration: u64,
+
+    #[arg(
+        long,
+        default_value_t = 15,
+        value_parser = clap::value_parser!(u32).range(1..=30),
+        help = "Frames per second kept in the file"
+    )]
+    pub fps: u32,
+
+    #[arg(
+        long,
+        default_value_t = 80,
+        value_parser = clap::value_parser!(u16).range(..=1000),
+        help = "Width in columns"
+    )]
+    pub width: u16,
+
+    #[arg(
+        long,
+        default_value_t = 24,
+        value_parser = clap::value_parser!(u16).range(..=500),
+        help = "Height in rows"
+    )]
+    pub height: u16,
+}
+
+impl RecordArgs {
+    /// Checks the output format and size before anything is set up.
+    pub fn validate(&self) -> Result<(), RecordError> {
+        RecordFormat::from_path(&self.out)?;
+        if self.width < MIN_TERMINAL_WIDTH || self.height < MIN_TERMINAL_HEIGHT {
+            return Err(RecordError::TooSmall {
+                width: self.width,
+                height: self.height,
+                min_width: MIN_TERMINAL_WIDTH,
+                min_height: MIN_TERMINAL_HEIGHT,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Records `app` into the output file and returns how many frames were kept.
+pub async fn run(
+    args: &RecordArgs,
+    app: &mut App,
+    renderer: &mut TerminalRenderer,
+) -> Result<u32, RecordError> {
+    let format = RecordFormat::from_path(&args.out)?;
+    let write_error = |source: io::Error| RecordError::WriteError {
+        path: args.out.display().to_string(),
+        source,
+    };
+
+    let file = File::create(&args.out).map_err(write_error)?;
+    let mut recorder = Recorder::new(
+        BufWriter::new(file),
+        format,
+        args.width,
+        args.height,
+        args.fps,
+    )
+    .map_err(write_error)?;
+
+    app.record(renderer, Duration::from_secs(args.duration), &mut recorder)
+        .await
+        .map_err(write_error)?;
+
+    let frames = recorder.frames();
+    recorder.finish().map_err(write_error)?;
+    Ok(frames)
+}
@@ -0,0 +1,82 @@
+use crate::app::App;
+use crate::error::SnapshotError;
+use crate::render::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, TerminalRenderer};
+use clap::Args;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone)]
+pub struct SnapshotArgs {
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        help = "Output file (default: standard output)"
+    )]
+    pub out: Option<PathBuf>,
+
+    #[arg(long, help = "Write plain text without color escapes")]
+    pub plain: bool,
+
+    #[arg(
+        long,
+        default_value_t = 80,
+        value_parser = clap::value_parser!(u16).range(..=1000),
+        help = "Width in columns"
+    )]
+    pub width: u16,
+
+    #[arg(
+        long,
+        default_value_t = 24,
+        value_parser = clap::value_parser!(u16).range(..=500),
+        help = "Height in rows"
+    )]
+    pub height: u16,
+}
+
+impl SnapshotArgs {
+    /// Checks the size before anything is set up.
+    pub fn validate(&self) -> Result<(), SnapshotError> {
+        if self.width < MIN_TERMINAL_WIDTH || self.height < MIN_TERMINAL_HEIGHT {
+            return Err(SnapshotError::TooSmall {
+                width: self.width,
+                height: self.height,
+                min_width: MIN_TERMINAL_WIDTH,
+                min_height: MIN_TERMINAL_HEIGHT,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Renders one frame of `app` and writes it to the output file or stdout.
+pub async fn run(
+    args: &SnapshotArgs,
+    app: &mut App,
+    renderer: &mut TerminalRenderer,
+) -> Result<(), SnapshotError> {
+    let path = args
+        .out
+        .as_ref()
+        .map_or_else(|| String::from("<stdout>"), |out| out.display().to_string());
+    let write_error = |source: io::Error| SnapshotError::WriteError {
+        path: path.clone(),
+        source,
+    };
+
+    app.snapshot(renderer).await.map_err(write_error)?;
+
+    let frame = if args.plain {
+        renderer.to_text()
+    } else {
+        renderer.to_ansi()
+    };
+    match args.out {
+        Some(ref out) => std::fs::write(out, frame).map_err(write_error),
+        None => io::stdout()
+            .lock()
+            .write_all(frame.as_bytes())
+            .map_err(write_error),
+    }
+}
@@ -3,6 +3,9 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::error::ConfigError;
+use crate::retry::RetryPolicy;
+use crate::simulate::SimulateParams;
+use crate::theme::ThemeSetting;
 use crate::weather::types::WeatherUnits;
 
 #[derive(Deserialize, Debug, Default, Clone)]
@@ -12,6 +15,13 @@ pub struct Config {
     #[serde(default)]
     pub hide_hud: bool,
     #[serde(default)]
+    pub sky_fill: bool,
+    #[serde(default)]
+    pub unicode: bool,
+    /// Seed for the animations' random numbers, for runs that replay the same way.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
     pub units: WeatherUnits,
     #[serde(default)]
     pub silent: bool,
@@ -19,24 +29,46 @@ pub struct Config {
     pub weather: WeatherConfig,
     #[serde(default)]
     pub shell: ShellConfig,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub simulate: SimulateParams,
+    #[serde(default)]
+    pub theme: Option<ThemeSetting>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
-pub struct ShellConfig {
+pub struct HistoryConfig {
     #[serde(default)]
-    pub background_mode: bool,
-    pub shell_path: Option<String>,
+    pub enabled: bool,
+    pub path: Option<PathBuf>,
+    #[serde(default = "default_retention_days")]
+    pub retention_days: u64,
+}
+
+fn default_retention_days() -> u64 {
+    7
 }
 
-impl Default for ShellConfig {
+impl Default for HistoryConfig {
     fn default() -> Self {
         Self {
-            background_mode: false,
-            shell_path: None,
+            enabled: false,
+            path: None,
+            retention_days: default_retention_days(),
         }
     }
 }
 
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ShellConfig {
+    #[serde(default)]
+    pub background_mode: bool,
+    pub shell_path: Option<String>,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct WeatherConfig {
     #[serde(default = "default_provider")]
@@ -124,7 +156,7 @@ impl Config {
             return Err(ConfigError::InvalidLongitude(self.location.longitude));
         }
 
-        Ok(())
+        self.simulate.validate()
     }
 
     pub fn load_from_path(path: &PathBuf) -> Result<Self, ConfigError> {
@@ -137,13 +169,22 @@ impl Config {
     }
 
     fn get_config_path() -> Result<PathBuf, ConfigError> {
+        Ok(Self::get_config_dir()?.join("config.toml"))
+    }
+
+    /// Where user themes live, one `<name>.toml` per theme.
+    pub fn get_themes_dir() -> Result<PathBuf, ConfigError> {
+        Ok(Self::get_config_dir()?.join("themes"))
+    }
+
+    fn get_config_dir() -> Result<PathBuf, ConfigError> {
         let config_dir = if let Ok(xdg_config) = std::env::var("XDG_CONFIG_HOME") {
             PathBuf::from(xdg_config)
         } else {
             dirs::config_dir().ok_or(ConfigError::NoConfigDir)?
         };
 
-        Ok(config_dir.join("weathr").join("config.toml"))
+        Ok(config_dir.join("weathr"))
     }
 }
 
@@ -272,10 +313,17 @@ longitude = 0.0
                 hide: false,
             },
             hide_hud: false,
+            sky_fill: false,
+            unicode: false,
+            seed: None,
             units: WeatherUnits::default(),
             silent: false,
             weather: WeatherConfig::default(),
             shell: ShellConfig::default(),
+            retry: RetryPolicy::default(),
+            history: HistoryConfig::default(),
+            simulate: SimulateParams::default(),
+            theme: None,
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -292,10 +340,17 @@ longitude = 0.0
                 hide: false,
             },
             hide_hud: false,
+            sky_fill: false,
+            unicode: false,
+            seed: None,
             units: WeatherUnits::default(),
             silent: false,
             weather: WeatherConfig::default(),
             shell: ShellConfig::default(),
+            retry: RetryPolicy::default(),
+            history: HistoryConfig::default(),
+            simulate: SimulateParams::default(),
+            theme: None,
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -312,10 +367,17 @@ longitude = 0.0
                 hide: false,
             },
             hide_hud: false,
+            sky_fill: false,
+            unicode: false,
+            seed: None,
             units: WeatherUnits::default(),
             silent: false,
             weather: WeatherConfig::default(),
             shell: ShellConfig::default(),
+            retry: RetryPolicy::default(),
+            history: HistoryConfig::default(),
+            simulate: SimulateParams::default(),
+            theme: None,
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -332,10 +394,17 @@ longitude = 0.0
                 hide: false,
             },
             hide_hud: false,
+            sky_fill: false,
+            unicode: false,
+            seed: None,
             units: WeatherUnits::default(),
             silent: false,
             weather: WeatherConfig::default(),
             shell: ShellConfig::default(),
+            retry: RetryPolicy::default(),
+            history: HistoryConfig::default(),
+            simulate: SimulateParams::default(),
+            theme: None,
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -352,15 +421,78 @@ longitude = 0.0
                 hide: false,
             },
             hide_hud: false,
+            sky_fill: false,
+            unicode: false,
+            seed: None,
             units: WeatherUnits::default(),
             silent: false,
             weather: WeatherConfig::default(),
             shell: ShellConfig::default(),
+            retry: RetryPolicy::default(),
+            history: HistoryConfig::default(),
+            simulate: SimulateParams::default(),
+            theme: None,
         };
         let result = config.validate();
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_config_retry_custom() {
+        let toml_content = r#"
+[retry]
+max_attempts = 5
+initial_delay_ms = 250
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.retry.max_attempts, 5);
+        assert_eq!(config.retry.initial_delay_ms, 250);
+        assert_eq!(config.retry.max_delay_ms, RetryPolicy::default().max_delay_ms);
+    }
+
+    #[test]
+    fn test_config_simulate_table() {
+        let toml_content = r#"
+[simulate]
+temperature = 22.5
+wind_direction = 270.0
+time_of_day = "21:30"
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert_eq!(config.simulate.temperature, Some(22.5));
+        assert_eq!(config.simulate.wind_direction, Some(270.0));
+        assert_eq!(
+            config.simulate.time_of_day,
+            Some(crate::simulate::TimeOfDay::Clock(
+                chrono::NaiveTime::from_hms_opt(21, 30, 0).unwrap()
+            ))
+        );
+        assert!(config.validate().is_ok());
+
+        let invalid: Config = toml::from_str("[simulate]\ncloud_cover = 120.0\n").unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_history() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.history.enabled);
+        assert_eq!(config.history.retention_days, 7);
+
+        let toml_content = r#"
+[history]
+enabled = true
+path = "/tmp/weathr-history.jsonl"
+"#;
+        let config: Config = toml::from_str(toml_content).unwrap();
+        assert!(config.history.enabled);
+        assert_eq!(
+            config.history.path,
+            Some(PathBuf::from("/tmp/weathr-history.jsonl"))
+        );
+        assert_eq!(config.history.retention_days, 7);
+    }
+
     #[test]
     fn test_config_units_default() {
         let toml_content = r#"
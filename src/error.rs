@@ -22,6 +22,18 @@ pub enum WeatherError {
     Shell(#[from] ShellError),
 }
 
+impl WeatherError {
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            WeatherError::Network(net_err) => net_err.is_retryable(),
+            WeatherError::Geolocation(GeolocationError::Unreachable(net_err)) => {
+                net_err.is_retryable()
+            }
+            _ => false,
+        }
+    }
+}
+
 #[derive(ThisError, Debug)]
 pub enum NetworkError {
     #[error("failed to create HTTP client: {0}")]
@@ -67,7 +79,8 @@ impl NetworkError {
                 timeout_secs,
             }
         } else if error.is_connect() {
-            let error_msg = error.to_string();
+            // The DNS/refused detail lives in the source chain, not the top-level message
+            let error_msg = error_chain_message(&error);
             if error_msg.contains("dns") || error_msg.contains("DNS") {
                 return NetworkError::DnsFailure {
                     url: url.to_string(),
@@ -133,6 +146,17 @@ impl NetworkError {
     }
 }
 
+fn error_chain_message(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+    }
+    message
+}
+
 #[derive(ThisError, Debug)]
 pub enum ConfigError {
     #[error("failed to read config file at {path}")]
@@ -153,6 +177,27 @@ pub enum ConfigError {
 
     #[error("invalid longitude: {0} (must be between -180 and 180)")]
     InvalidLongitude(f64),
+
+    #[error("invalid simulated {field}: {value} (must be between {min} and {max})")]
+    InvalidSimulation {
+        field: &'static str,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+
+    #[error("unknown theme '{0}' (not a built-in theme or a file in the themes directory)")]
+    UnknownTheme(String),
+
+    #[error("invalid colors in theme '{name}'")]
+    InvalidTheme {
+        name: String,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("theme '{0}' nests too many base themes (is there a loop?)")]
+    ThemeTooDeep(String),
 }
 
 impl ConfigError {
@@ -164,6 +209,10 @@ impl ConfigError {
             ConfigError::NoConfigDir => "NoConfigDir",
             ConfigError::InvalidLatitude(_) => "InvalidLatitude",
             ConfigError::InvalidLongitude(_) => "InvalidLongitude",
+            ConfigError::InvalidSimulation { .. } => "InvalidSimulation",
+            ConfigError::UnknownTheme(_) => "UnknownTheme",
+            ConfigError::InvalidTheme { .. } => "InvalidTheme",
+            ConfigError::ThemeTooDeep(_) => "ThemeTooDeep",
         }
     }
 }
@@ -230,8 +279,12 @@ pub enum GeolocationError {
     #[error("failed to parse location data: {0}")]
     ParseError(String),
 
-    #[error("failed after {attempts} retry attempts")]
-    RetriesExhausted { attempts: u32 },
+    #[error("failed after {attempts} retry attempts: {last_error}")]
+    RetriesExhausted {
+        attempts: u32,
+        #[source]
+        last_error: NetworkError,
+    },
 }
 
 impl GeolocationError {
@@ -275,9 +328,12 @@ impl GeolocationError {
             GeolocationError::ParseError(_) => "Received invalid location data.\n\
                  Using configured/default location."
                 .to_string(),
-            GeolocationError::RetriesExhausted { attempts } => {
+            GeolocationError::RetriesExhausted {
+                attempts,
+                last_error,
+            } => {
                 format!(
-                    "Failed to detect location after {attempts} attempts.\n\
+                    "Failed to detect location after {attempts} attempts: {last_error}.\n\
                      Using configured/default location."
                 )
             }
@@ -285,6 +341,113 @@ impl GeolocationError {
     }
 }
 
+#[derive(ThisError, Debug)]
+pub enum CacheError {
+    #[error("could not determine cache directory (check $XDG_CACHE_HOME or $HOME)")]
+    NoCacheDir,
+
+    #[error("failed to write cache file at {path}")]
+    WriteError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to remove cache file at {path}")]
+    RemoveError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to evict old cache entries in {path}")]
+    EvictError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to serialize cache entry")]
+    SerializeError(#[from] serde_json::Error),
+}
+
+#[derive(ThisError, Debug)]
+pub enum HistoryError {
+    #[error("could not determine data directory (check $XDG_DATA_HOME or $HOME)")]
+    NoDataDir,
+
+    #[error("failed to write weather history at {path}")]
+    WriteError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to serialize weather history record")]
+    SerializeError(#[from] serde_json::Error),
+}
+
+#[derive(ThisError, Debug)]
+pub enum ReplayError {
+    #[error("failed to read replay file at {path}")]
+    ReadError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to parse replay file at {path}: {message}")]
+    ParseError { path: String, message: String },
+
+    #[error("invalid frame time {0} (use seconds or \"HH:MM[:SS]\")")]
+    InvalidOffset(String),
+
+    #[error("replay file at {0} has no frames")]
+    Empty(String),
+
+    #[error("unsupported replay file {0} (expected .toml, .json or .jsonl)")]
+    UnsupportedFormat(String),
+}
+
+#[derive(ThisError, Debug)]
+pub enum RecordError {
+    #[error("unsupported recording file {0} (expected .cast, .svg or .gif)")]
+    UnsupportedFormat(String),
+
+    #[error("recording size {width}x{height} is too small (minimum: {min_width}x{min_height})")]
+    TooSmall {
+        width: u16,
+        height: u16,
+        min_width: u16,
+        min_height: u16,
+    },
+
+    #[error("failed to write recording at {path}")]
+    WriteError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+#[derive(ThisError, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot size {width}x{height} is too small (minimum: {min_width}x{min_height})")]
+    TooSmall {
+        width: u16,
+        height: u16,
+        min_width: u16,
+        min_height: u16,
+    },
+
+    #[error("failed to write snapshot at {path}")]
+    WriteError {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
 #[derive(ThisError, Debug)]
 pub enum ShellError {
     #[error("failed to create PTY: {0}")]
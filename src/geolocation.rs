@@ -1,11 +1,10 @@
 use crate::cache;
 use crate::error::{GeolocationError, NetworkError};
+use crate::retry::RetryPolicy;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 const IPINFO_URL: &str = "https://ipinfo.io/json";
-const MAX_RETRIES: u32 = 3;
-const INITIAL_RETRY_DELAY_MS: u64 = 500;
 
 #[derive(Deserialize, Debug)]
 struct IpInfoResponse {
@@ -20,42 +19,36 @@ pub struct GeoLocation {
     pub city: Option<String>,
 }
 
-pub async fn detect_location() -> Result<GeoLocation, GeolocationError> {
+pub async fn detect_location(retry_policy: &RetryPolicy) -> Result<GeoLocation, GeolocationError> {
     if let Some(cached) = cache::load_cached_location().await {
         return Ok(cached);
     }
 
-    detect_location_with_retry().await
+    detect_location_with_retry(retry_policy).await
 }
 
-async fn detect_location_with_retry() -> Result<GeoLocation, GeolocationError> {
-    let mut last_error = None;
-
-    for attempt in 1..=MAX_RETRIES {
-        match fetch_location().await {
-            Ok(location) => return Ok(location),
-            Err(e) => {
-                let should_retry = matches!(
-                    e,
-                    GeolocationError::Unreachable(ref net_err) if net_err.is_retryable()
-                );
-
-                if !should_retry || attempt == MAX_RETRIES {
-                    return Err(e);
+async fn detect_location_with_retry(
+    retry_policy: &RetryPolicy,
+) -> Result<GeoLocation, GeolocationError> {
+    // A retryable error coming back means every attempt was spent on transient failures
+    retry_policy
+        .run(fetch_location, is_retryable)
+        .await
+        .map_err(|e| match e {
+            GeolocationError::Unreachable(last_error)
+                if last_error.is_retryable() && retry_policy.attempts() > 1 =>
+            {
+                GeolocationError::RetriesExhausted {
+                    attempts: retry_policy.attempts(),
+                    last_error,
                 }
-
-                let delay_ms = INITIAL_RETRY_DELAY_MS * 2_u64.pow(attempt - 1);
-                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
-                last_error = Some(e);
             }
-        }
-    }
+            e => e,
+        })
+}
 
-    Err(
-        last_error.unwrap_or_else(|| GeolocationError::RetriesExhausted {
-            attempts: MAX_RETRIES,
-        }),
-    )
+fn is_retryable(error: &GeolocationError) -> bool {
+    matches!(error, GeolocationError::Unreachable(net_err) if net_err.is_retryable())
 }
 
 async fn fetch_location() -> Result<GeoLocation, GeolocationError> {
@@ -94,7 +87,9 @@ async fn fetch_location() -> Result<GeoLocation, GeolocationError> {
         city: ip_info.city,
     };
 
-    cache::save_location_cache(&location);
+    if let Err(e) = cache::save_location_cache(&location).await {
+        eprintln!("Warning: failed to cache detected location: {}", e);
+    }
 
     Ok(location)
 }
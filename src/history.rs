@@ -0,0 +1,409 @@
+use crate::cache::write_atomic;
+use crate::error::HistoryError;
+use crate::weather::{WeatherData, WeatherUnits, format_temperature};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// How far back the HUD sparklines look.
+pub const HISTORY_WINDOW_SECS: u64 = 24 * 3600;
+
+const SPARKLINE_WIDTH: usize = 24;
+const SPARKLINE_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Lowest to highest, for terminals that cannot show the block characters.
+const SPARKLINE_ASCII_CHARS: [char; 5] = ['_', '.', '-', '=', '#'];
+
+/// Pressure tendency is conventionally reported as the change over three hours.
+const PRESSURE_TREND_WINDOW_SECS: u64 = 3 * 3600;
+const PRESSURE_TREND_MIN_SPAN_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub recorded_at: u64,
+    pub data: WeatherData,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureTrend {
+    RisingFast,
+    Rising,
+    Steady,
+    Falling,
+    FallingFast,
+}
+
+impl PressureTrend {
+    /// Classifies a pressure change in hPa per three hours.
+    pub fn from_change(change_hpa: f64) -> Self {
+        if change_hpa <= -3.0 {
+            Self::FallingFast
+        } else if change_hpa <= -1.0 {
+            Self::Falling
+        } else if change_hpa >= 3.0 {
+            Self::RisingFast
+        } else if change_hpa >= 1.0 {
+            Self::Rising
+        } else {
+            Self::Steady
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::RisingFast => "rising fast",
+            Self::Rising => "rising",
+            Self::Steady => "steady",
+            Self::Falling => "falling",
+            Self::FallingFast => "falling fast",
+        }
+    }
+}
+
+/// Append-only JSONL log of every weather reading fetched from a provider.
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl HistoryStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        let data_dir = if let Ok(xdg_data) = std::env::var("XDG_DATA_HOME") {
+            PathBuf::from(xdg_data)
+        } else {
+            dirs::data_dir()?
+        };
+        Some(data_dir.join("weathr").join("history.jsonl"))
+    }
+
+    fn write_error(&self, source: std::io::Error) -> HistoryError {
+        HistoryError::WriteError {
+            path: self.path.display().to_string(),
+            source,
+        }
+    }
+
+    pub async fn append(&self, data: &WeatherData) -> Result<(), HistoryError> {
+        self.append_record(&HistoryRecord {
+            recorded_at: current_timestamp(),
+            data: data.clone(),
+        })
+        .await
+    }
+
+    async fn append_record(&self, record: &HistoryRecord) -> Result<(), HistoryError> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|e| self.write_error(e))?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|e| self.write_error(e))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|e| self.write_error(e))?;
+        file.flush().await.map_err(|e| self.write_error(e))
+    }
+
+    /// Loads records recorded at or after `since`, oldest first. Malformed lines
+    /// (e.g. a write cut short by a crash) are skipped.
+    pub async fn load_since(&self, since: u64) -> Vec<HistoryRecord> {
+        let Ok(contents) = fs::read_to_string(&self.path).await else {
+            return Vec::new();
+        };
+
+        let mut records: Vec<HistoryRecord> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<HistoryRecord>(line).ok())
+            .filter(|record| record.recorded_at >= since)
+            .collect();
+        records.sort_by_key(|record| record.recorded_at);
+        records
+    }
+
+    /// Drops records older than the retention period, rewriting the log atomically.
+    pub async fn prune(&self, retention_secs: u64) -> Result<(), HistoryError> {
+        let Ok(contents) = fs::read_to_string(&self.path).await else {
+            return Ok(());
+        };
+
+        let cutoff = current_timestamp().saturating_sub(retention_secs);
+        let mut kept = String::with_capacity(contents.len());
+        let mut dropped = false;
+        for line in contents.lines() {
+            match serde_json::from_str::<HistoryRecord>(line) {
+                Ok(record) if record.recorded_at >= cutoff => {
+                    kept.push_str(line);
+                    kept.push('\n');
+                }
+                _ => dropped = true,
+            }
+        }
+        if !dropped {
+            return Ok(());
+        }
+
+        write_atomic(&self.path, kept.as_bytes())
+            .await
+            .map_err(|e| self.write_error(e))
+    }
+}
+
+/// Renders samples as a block sparkline scaled to their own range, or an ASCII
+/// one unless `utf8`. Missing samples are left blank so gaps in the record stay
+/// visible.
+pub fn sparkline(samples: &[Option<f64>], utf8: bool) -> String {
+    let chars: &[char] = if utf8 {
+        &SPARKLINE_CHARS
+    } else {
+        &SPARKLINE_ASCII_CHARS
+    };
+    let present = samples.iter().flatten();
+    let min = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    samples
+        .iter()
+        .map(|sample| match sample {
+            None => ' ',
+            Some(_) if range < f64::EPSILON => chars[chars.len() / 2],
+            Some(value) => {
+                let level = ((value - min) / range * (chars.len() - 1) as f64).round();
+                chars[level as usize]
+            }
+        })
+        .collect()
+}
+
+/// Averages a value over `buckets` equal time slices of the window ending at `now`.
+fn bucketed(
+    records: &[HistoryRecord],
+    now: u64,
+    buckets: usize,
+    value: impl Fn(&WeatherData) -> f64,
+) -> Vec<Option<f64>> {
+    let start = now.saturating_sub(HISTORY_WINDOW_SECS);
+    let bucket_secs = (HISTORY_WINDOW_SECS / buckets as u64).max(1);
+    let mut sums = vec![(0.0, 0u32); buckets];
+
+    for record in records {
+        if record.recorded_at < start || record.recorded_at > now {
+            continue;
+        }
+        let idx = (((record.recorded_at - start) / bucket_secs) as usize).min(buckets - 1);
+        sums[idx].0 += value(&record.data);
+        sums[idx].1 += 1;
+    }
+
+    sums.into_iter()
+        .map(|(sum, count)| (count > 0).then(|| sum / count as f64))
+        .collect()
+}
+
+/// Pressure change over roughly the last three hours, scaled to hPa per three hours.
+pub fn pressure_trend(records: &[HistoryRecord], now: u64) -> Option<(PressureTrend, f64)> {
+    let latest = records.iter().rfind(|r| r.recorded_at <= now)?;
+    let target = latest
+        .recorded_at
+        .saturating_sub(PRESSURE_TREND_WINDOW_SECS);
+    let earlier = records
+        .iter()
+        .filter(|r| r.recorded_at + PRESSURE_TREND_MIN_SPAN_SECS <= latest.recorded_at)
+        .min_by_key(|r| r.recorded_at.abs_diff(target))?;
+
+    let span = (latest.recorded_at - earlier.recorded_at) as f64;
+    let change =
+        (latest.data.pressure - earlier.data.pressure) * PRESSURE_TREND_WINDOW_SECS as f64 / span;
+    Some((PressureTrend::from_change(change), change))
+}
+
+/// Sparkline panel lines for the last 24 hours, or nothing if there is too little data.
+pub fn panel_lines(
+    records: &[HistoryRecord],
+    now: u64,
+    units: &WeatherUnits,
+    utf8: bool,
+) -> Vec<String> {
+    let Some(latest) = records.last() else {
+        return Vec::new();
+    };
+    if records.len() < 2 {
+        return Vec::new();
+    }
+
+    let temps = bucketed(records, now, SPARKLINE_WIDTH, |d| d.temperature);
+    let pressures = bucketed(records, now, SPARKLINE_WIDTH, |d| d.pressure);
+    let humidities = bucketed(records, now, SPARKLINE_WIDTH, |d| d.humidity);
+
+    let present = temps.iter().flatten();
+    let min_temp = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let max_temp = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    let (min_temp, temp_unit) = format_temperature(min_temp, units.temperature);
+    let (max_temp, _) = format_temperature(max_temp, units.temperature);
+
+    let trend = match pressure_trend(records, now) {
+        Some((trend, change)) => format!("{} ({:+.1} hPa/3h)", trend.label(), change),
+        None => "trend pending".to_string(),
+    };
+
+    vec![
+        format!(
+            "Temp  24h {} {:.1}..{:.1}{}",
+            sparkline(&temps, utf8),
+            min_temp,
+            max_temp,
+            temp_unit
+        ),
+        format!(
+            "Press 24h {} {:.0} hPa, {}",
+            sparkline(&pressures, utf8),
+            latest.data.pressure,
+            trend
+        ),
+        format!(
+            "Humid 24h {} {:.0}%",
+            sparkline(&humidities, utf8),
+            latest.data.humidity
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn record(recorded_at: u64, temperature: f64, pressure: f64) -> HistoryRecord {
+        HistoryRecord {
+            recorded_at,
+            data: WeatherData {
+                condition: WeatherCondition::Clear,
+                temperature,
+                apparent_temperature: temperature,
+                humidity: 60.0,
+                precipitation: 0.0,
+                wind_speed: 3.0,
+                wind_direction: 90.0,
+                cloud_cover: 10.0,
+                pressure,
+                visibility: Some(10000.0),
+                is_day: true,
+                moon_phase: Some(0.5),
+                timestamp: recorded_at.to_string(),
+                precipitation_level: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_sparkline_scales_and_keeps_gaps() {
+        let line = sparkline(&[Some(0.0), None, Some(3.5), Some(7.0)], true);
+        assert_eq!(line, "▁ ▅█");
+
+        let flat = sparkline(&[Some(5.0), Some(5.0)], true);
+        assert_eq!(flat, "▅▅");
+
+        let ascii = sparkline(&[Some(0.0), None, Some(3.5), Some(7.0)], false);
+        assert_eq!(ascii, "_ -#");
+    }
+
+    #[test]
+    fn test_pressure_trend_classification() {
+        assert_eq!(PressureTrend::from_change(-4.0), PressureTrend::FallingFast);
+        assert_eq!(PressureTrend::from_change(-1.5), PressureTrend::Falling);
+        assert_eq!(PressureTrend::from_change(0.4), PressureTrend::Steady);
+        assert_eq!(PressureTrend::from_change(2.0), PressureTrend::Rising);
+        assert_eq!(PressureTrend::from_change(3.0), PressureTrend::RisingFast);
+    }
+
+    #[test]
+    fn test_pressure_trend_scales_to_three_hours() {
+        let now = 100_000;
+        let records = vec![
+            record(now - 3 * 3600, 10.0, 1012.0),
+            record(now - 3600, 10.0, 1008.0),
+            record(now, 10.0, 1006.0),
+        ];
+        let (trend, change) = pressure_trend(&records, now).unwrap();
+        assert_eq!(trend, PressureTrend::FallingFast);
+        assert!((change - -6.0).abs() < 1e-9);
+
+        // Readings closer together than an hour are too noisy for a trend
+        let recent = vec![record(now - 600, 10.0, 1012.0), record(now, 10.0, 1000.0)];
+        assert!(pressure_trend(&recent, now).is_none());
+    }
+
+    #[test]
+    fn test_panel_lines_need_two_records() {
+        let now = 100_000;
+        let units = WeatherUnits::default();
+        assert!(panel_lines(&[record(now, 10.0, 1010.0)], now, &units, true).is_empty());
+
+        let records = [
+            record(now - 4 * 3600, 8.0, 1015.0),
+            record(now, 14.0, 1010.0),
+        ];
+        let lines = panel_lines(&records, now, &units, true);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("8.0..14.0°C"));
+        assert!(lines[1].contains("1010 hPa, falling"));
+        assert!(lines[2].contains("60%"));
+
+        let lines = panel_lines(&records, now, &units, false);
+        assert!(lines[1].is_ascii() && lines[2].is_ascii());
+    }
+
+    #[tokio::test]
+    async fn test_append_load_and_prune() {
+        let path =
+            std::env::temp_dir().join(format!("weathr_history_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store = HistoryStore::new(path.clone());
+        let now = current_timestamp();
+
+        store
+            .append_record(&record(now - 10 * 86400, 5.0, 1000.0))
+            .await
+            .unwrap();
+        store
+            .append_record(&record(now - 60, 15.0, 1010.0))
+            .await
+            .unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| std::io::Write::write_all(&mut f, b"{truncated\n"))
+            .unwrap();
+
+        assert_eq!(store.load_since(0).await.len(), 2);
+        assert_eq!(store.load_since(now - 3600).await.len(), 1);
+
+        store.prune(7 * 86400).await.unwrap();
+        let remaining = store.load_since(0).await;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].data.temperature, 15.0);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
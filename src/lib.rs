@@ -3,7 +3,14 @@ pub mod cache;
 pub mod config;
 pub mod error;
 pub mod geolocation;
+pub mod history;
+pub mod record;
 pub mod render;
+pub mod replay;
+pub mod retry;
 pub mod scene;
+pub mod season;
 pub mod shell;
+pub mod simulate;
+pub mod theme;
 pub mod weather;
@@ -3,15 +3,23 @@ mod animation_manager;
 mod app;
 mod app_state;
 mod cache;
+mod commands;
 mod config;
 mod error;
 mod geolocation;
+mod history;
+mod record;
 mod render;
+mod replay;
+mod retry;
 mod scene;
+mod season;
 mod shell;
+mod simulate;
+mod theme;
 mod weather;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use config::Config;
 use crossterm::{
     cursor, execute,
@@ -20,6 +28,11 @@ use crossterm::{
 };
 use error::WeatherError;
 use render::TerminalRenderer;
+use replay::ReplayTimeline;
+use season::Season;
+use simulate::{SimulateParams, TimeOfDay};
+use theme::{Theme, ThemeSetting};
+use std::path::PathBuf;
 use std::{io, panic};
 
 const LONG_VERSION: &str = concat!(
@@ -36,9 +49,25 @@ fn info(silent: bool, msg: &str) {
     }
 }
 
+/// Accepts a finite `--replay-speed` within the range replays can be scheduled at.
+fn parse_replay_speed(value: &str) -> Result<f64, String> {
+    let speed: f64 = value.parse().map_err(|e| format!("{e}"))?;
+    if !(replay::MIN_SPEED..=replay::MAX_SPEED).contains(&speed) {
+        return Err(format!(
+            "must be between {} and {}",
+            replay::MIN_SPEED,
+            replay::MAX_SPEED
+        ));
+    }
+    Ok(speed)
+}
+
 #[derive(Parser)]
 #[command(version, long_version = LONG_VERSION, about = "Terminal-based ASCII weather application", long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(
         short,
         long,
@@ -47,6 +76,24 @@ struct Cli {
     )]
     simulate: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "simulate",
+        help = "Replay a recorded history log (.jsonl) or scripted timeline (.toml/.json)"
+    )]
+    replay: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FACTOR",
+        default_value_t = 1.0,
+        requires = "replay",
+        value_parser = parse_replay_speed,
+        help = "Replay speed multiplier (e.g. 1440 plays a day in a minute)"
+    )]
+    replay_speed: f64,
+
     #[arg(
         short,
         long,
@@ -54,9 +101,89 @@ struct Cli {
     )]
     night: bool,
 
-    #[arg(short, long, help = "Enable falling autumn leaves")]
+    #[arg(
+        long,
+        value_name = "DAY|NIGHT|HH:MM",
+        requires = "simulate",
+        conflicts_with = "night",
+        help = "Simulated time of day"
+    )]
+    time_of_day: Option<TimeOfDay>,
+
+    #[arg(
+        long,
+        value_name = "CELSIUS",
+        allow_negative_numbers = true,
+        requires = "simulate",
+        help = "Simulated temperature in °C"
+    )]
+    temperature: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "M/S",
+        requires = "simulate",
+        help = "Simulated wind speed in m/s"
+    )]
+    wind_speed: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "DEGREES",
+        requires = "simulate",
+        help = "Simulated wind direction (degrees the wind blows from, 0 = north)"
+    )]
+    wind_direction: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "MM",
+        requires = "simulate",
+        help = "Simulated precipitation in mm"
+    )]
+    precipitation: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        requires = "simulate",
+        help = "Simulated cloud cover (0-100)"
+    )]
+    cloud_cover: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "METERS",
+        requires = "simulate",
+        help = "Simulated visibility in meters"
+    )]
+    visibility: Option<f64>,
+
+    #[arg(
+        long,
+        value_name = "PHASE",
+        requires = "simulate",
+        help = "Simulated moon phase (0 = new, 0.5 = full, 1 = new)"
+    )]
+    moon_phase: Option<f64>,
+
+    #[arg(short, long, help = "Enable falling leaves all year (they always fall in autumn)")]
     leaves: bool,
 
+    #[arg(
+        long,
+        value_name = "SEASON",
+        help = "Show a fixed season (spring, summer, autumn, winter) instead of the current one"
+    )]
+    season: Option<Season>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Color theme (default, solarized, gruvbox, nord, high-contrast, or a file in the themes directory)"
+    )]
+    theme: Option<String>,
+
     #[arg(long, help = "Auto-detect location via IP (uses ipinfo.io)")]
     auto_location: bool,
 
@@ -66,6 +193,22 @@ struct Cli {
     #[arg(long, help = "Hide HUD (status line)")]
     hide_hud: bool,
 
+    #[arg(long, help = "Paint the sky with a background gradient for the time of day and weather")]
+    sky_fill: bool,
+
+    #[arg(
+        long,
+        help = "Draw with Unicode braille and box-drawing characters (needs a UTF-8 locale)"
+    )]
+    unicode: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Seed the random number generator so the animation plays out the same way each run"
+    )]
+    seed: Option<u64>,
+
     #[arg(
         long,
         conflicts_with = "metric",
@@ -95,6 +238,24 @@ struct Cli {
         help = "Shell to run in background mode (auto-detect if not specified)"
     )]
     shell: Option<String>,
+
+    #[arg(long, help = "Record weather history and show 24h trend sparklines")]
+    history: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Inspect or clear cached location and weather data
+    Cache {
+        #[command(subcommand)]
+        action: commands::cache::CacheCommand,
+    },
+
+    /// Record the scene off-screen to an asciicast, SVG or GIF file
+    Record(commands::record::RecordArgs),
+
+    /// Render one frame of the scene to an ANSI or plain text file
+    Snapshot(commands::snapshot::SnapshotArgs),
 }
 
 #[tokio::main]
@@ -148,6 +309,24 @@ async fn main() -> io::Result<()> {
         }
     };
 
+    if let Some(Command::Cache { action }) = cli.command {
+        if let Err(e) = commands::cache::run(action).await {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let offscreen_error = match cli.command {
+        Some(Command::Record(ref args)) => args.validate().err().map(|e| e.to_string()),
+        Some(Command::Snapshot(ref args)) => args.validate().err().map(|e| e.to_string()),
+        _ => None,
+    };
+    if let Some(e) = offscreen_error {
+        eprintln!("\nError: {}\n", e);
+        std::process::exit(1);
+    }
+
     let mut config = match Config::load() {
         Ok(config) => config,
         Err(e) => {
@@ -178,6 +357,15 @@ async fn main() -> io::Result<()> {
     if cli.hide_hud {
         config.hide_hud = true;
     }
+    if cli.sky_fill {
+        config.sky_fill = true;
+    }
+    if cli.unicode {
+        config.unicode = true;
+    }
+    if cli.seed.is_some() {
+        config.seed = cli.seed;
+    }
     if cli.imperial {
         config.units = weather::WeatherUnits::imperial();
     }
@@ -193,11 +381,79 @@ async fn main() -> io::Result<()> {
     if cli.shell.is_some() {
         config.shell.shell_path = cli.shell.clone();
     }
+    if cli.history {
+        config.history.enabled = true;
+    }
+    // Recordings and snapshots have no one to type into the shell
+    if cli.command.is_some() {
+        config.shell.background_mode = false;
+    }
+    // Keep progress messages out of a snapshot written to stdout
+    if let Some(Command::Snapshot(ref args)) = cli.command
+        && args.out.is_none()
+    {
+        config.silent = true;
+    }
+    if let Some(ref name) = cli.theme {
+        config.theme = Some(ThemeSetting::Name(name.clone()));
+    }
+    config.simulate.merge(SimulateParams {
+        temperature: cli.temperature,
+        wind_speed: cli.wind_speed,
+        wind_direction: cli.wind_direction,
+        precipitation: cli.precipitation,
+        cloud_cover: cli.cloud_cover,
+        visibility: cli.visibility,
+        moon_phase: cli.moon_phase,
+        time_of_day: cli.time_of_day.or(cli.night.then_some(TimeOfDay::Night)),
+    });
+    if let Err(e) = config.simulate.validate() {
+        eprintln!("\nError: {}\n", e);
+        std::process::exit(1);
+    }
+
+    let theme = match config.theme {
+        Some(ref setting) => match Theme::load(setting) {
+            Ok(theme) => theme,
+            Err(e) => {
+                match std::error::Error::source(&e) {
+                    Some(source) => eprintln!("\nError loading theme: {}\n{}", e, source),
+                    None => eprintln!("\nError loading theme: {}\n", e),
+                }
+                std::process::exit(1);
+            }
+        },
+        None => Theme::default(),
+    };
+
+    let replay = match cli.replay {
+        Some(ref path) => {
+            match ReplayTimeline::load(path) {
+                Ok(timeline) => {
+                    info(
+                        config.silent,
+                        &format!(
+                            "Replaying {} frames from {} at {}x",
+                            timeline.frames().len(),
+                            timeline.source(),
+                            cli.replay_speed
+                        ),
+                    );
+                    Some(timeline.with_speed(cli.replay_speed))
+                }
+                Err(e) => {
+                    eprintln!("\nError loading replay: {}\n", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
 
     // Auto-detect location if enabled
     if config.location.auto {
         info(config.silent, "Auto-detecting location...");
-        match geolocation::detect_location().await {
+        match geolocation::detect_location(&config.retry).await {
             Ok(geo_loc) => {
                 if let Some(city) = &geo_loc.city {
                     info(
@@ -225,8 +481,8 @@ async fn main() -> io::Result<()> {
         }
     }
 
-    // Validate weather provider before starting UI (skip for simulation mode)
-    if cli.simulate.is_none() {
+    // Validate weather provider before starting UI (skip for simulation and replay)
+    if cli.simulate.is_none() && replay.is_none() {
         if let Err(e) = app::App::validate_provider(&config).await {
             let error_msg = match &e {
                 WeatherError::Network(net_err) => net_err.user_friendly_message(),
@@ -239,26 +495,39 @@ async fn main() -> io::Result<()> {
         }
     }
 
-    let mut renderer = match TerminalRenderer::new() {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("\n{}\n", e.user_friendly_message());
-            std::process::exit(1);
-        }
+    let mut renderer = match cli.command {
+        Some(Command::Record(ref args)) => TerminalRenderer::headless(args.width, args.height),
+        Some(Command::Snapshot(ref args)) => TerminalRenderer::headless(args.width, args.height),
+        _ => match TerminalRenderer::new() {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("\n{}\n", e.user_friendly_message());
+                std::process::exit(1);
+            }
+        },
     };
 
+    if config.unicode && !renderer.set_unicode(true) {
+        info(
+            config.silent,
+            "Locale is not UTF-8, drawing with ASCII instead of Unicode",
+        );
+    }
+
     if let Err(e) = renderer.init() {
         eprintln!("\n{}\n", e.user_friendly_message());
         std::process::exit(1);
     };
+    renderer.set_theme(theme);
 
     let (term_width, term_height) = renderer.get_size();
 
     let mut app = match app::App::new(
         &config,
         cli.simulate,
-        cli.night,
+        replay,
         cli.leaves,
+        cli.season,
         term_width,
         term_height,
     ) {
@@ -270,6 +539,44 @@ async fn main() -> io::Result<()> {
         }
     };
 
+    if let Some(Command::Snapshot(ref args)) = cli.command {
+        if let Err(e) = commands::snapshot::run(args, &mut app, &mut renderer).await {
+            match std::error::Error::source(&e) {
+                Some(source) => eprintln!("\nError: {}\n{}", e, source),
+                None => eprintln!("\nError: {}\n", e),
+            }
+            std::process::exit(1);
+        }
+        if let Some(ref out) = args.out {
+            info(
+                config.silent,
+                &format!("Saved snapshot to {}", out.display()),
+            );
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Record(ref args)) = cli.command {
+        info(
+            config.silent,
+            &format!("Recording {}s to {}...", args.duration, args.out.display()),
+        );
+        match commands::record::run(args, &mut app, &mut renderer).await {
+            Ok(frames) => info(
+                config.silent,
+                &format!("Saved {} frames to {}", frames, args.out.display()),
+            ),
+            Err(e) => {
+                match std::error::Error::source(&e) {
+                    Some(source) => eprintln!("\nError: {}\n{}", e, source),
+                    None => eprintln!("\nError: {}\n", e),
+                }
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
     let result = tokio::select! {
         res = app.run(&mut renderer) => res,
         _ = tokio::signal::ctrl_c() => {
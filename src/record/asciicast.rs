@@ -0,0 +1,77 @@
+use serde_json::json;
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Hides the cursor so players do not draw it over the scene.
+const HIDE_CURSOR: &str = "\x1b[?25l";
+
+/// Writes an asciicast v2 recording: a JSON header line, then one
+/// `[time, "o", output]` event line per frame of terminal output.
+pub struct AsciicastWriter<W: Write> {
+    out: W,
+    started: bool,
+}
+
+impl<W: Write> AsciicastWriter<W> {
+    pub fn new(mut out: W, columns: u16, rows: u16) -> io::Result<Self> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let header = json!({
+            "version": 2,
+            "width": columns,
+            "height": rows,
+            "timestamp": timestamp,
+            "env": { "TERM": "xterm-256color" },
+        });
+        writeln!(out, "{header}")?;
+        Ok(Self {
+            out,
+            started: false,
+        })
+    }
+
+    /// Adds the escape sequences a frame wrote, to be played at `time`.
+    pub fn frame(&mut self, time: Duration, output: &[u8]) -> io::Result<()> {
+        let mut data = String::from_utf8_lossy(output).into_owned();
+        if !self.started {
+            data.insert_str(0, HIDE_CURSOR);
+            self.started = true;
+        }
+        if data.is_empty() {
+            return Ok(());
+        }
+        let seconds = time.as_millis() as f64 / 1000.0;
+        writeln!(self.out, "{}", json!([seconds, "o", data]))
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asciicast_lines() {
+        let mut cast = AsciicastWriter::new(Vec::new(), 80, 24).unwrap();
+        cast.frame(Duration::ZERO, b"\x1b[1;1Hhi").unwrap();
+        cast.frame(Duration::from_millis(500), b"").unwrap();
+        cast.frame(Duration::from_millis(1500), b"\"x\"").unwrap();
+        let text = String::from_utf8(cast.finish().unwrap()).unwrap();
+        let lines: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["version"], 2);
+        assert_eq!(lines[0]["width"], 80);
+        assert_eq!(lines[0]["height"], 24);
+        assert_eq!(lines[1], json!([0.0, "o", "\x1b[?25l\x1b[1;1Hhi"]));
+        assert_eq!(lines[2], json!([1.5, "o", "\"x\""]));
+    }
+}
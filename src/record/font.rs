@@ -0,0 +1,202 @@
+//! A built-in bitmap font for rasterizing cells: the classic 5x7 ASCII set,
+//! plus the Unicode glyphs the scene draws with, built from their shapes.
+
+/// Pixels per cell, before scaling.
+pub const CELL_WIDTH: usize = 6;
+pub const CELL_HEIGHT: usize = 12;
+
+/// Rows of the cell above the 5x7 ASCII glyphs.
+const ASCII_TOP: usize = 2;
+
+const FULL_ROW: u8 = 0b11_1111;
+
+/// Printable ASCII from ' ' to '~', one byte per column, bit 0 at the top.
+const ASCII_5X7: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // F
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x03, 0x04, 0x78, 0x04, 0x03], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // \
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // j
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+/// The pixel rows of `ch`, top to bottom, with bit `x` set for a lit pixel in
+/// column `x`. Characters the font has no shape for are drawn as a hollow box.
+pub fn glyph(ch: char) -> [u8; CELL_HEIGHT] {
+    let mut rows = [0; CELL_HEIGHT];
+    match ch {
+        ' '..='~' => {
+            let columns = &ASCII_5X7[ch as usize - ' ' as usize];
+            for (row, bits) in rows[ASCII_TOP..ASCII_TOP + 7].iter_mut().enumerate() {
+                for (x, column) in columns.iter().enumerate() {
+                    if column >> row & 1 == 1 {
+                        *bits |= 1 << x;
+                    }
+                }
+            }
+        }
+        '°' => rows[1..4].copy_from_slice(&[0b0110, 0b1001, 0b0110]),
+        '·' => rows[5..7].fill(0b1100),
+        // Lower blocks, one to eight eighths high
+        '\u{2581}'..='\u{2588}' => {
+            let eighths = ch as usize - 0x2580;
+            let lit = (eighths * CELL_HEIGHT + 4) / 8;
+            rows[CELL_HEIGHT - lit..].fill(FULL_ROW);
+        }
+        '\u{2800}'..='\u{28FF}' => braille(ch as u32 - 0x2800, &mut rows),
+        '│' => rows.fill(0b00_1100),
+        '─' => rows[5..7].fill(FULL_ROW),
+        '═' => {
+            rows[4] = FULL_ROW;
+            rows[7] = FULL_ROW;
+        }
+        '┼' => {
+            rows.fill(0b00_1100);
+            rows[5..7].fill(FULL_ROW);
+        }
+        _ => {
+            rows[ASCII_TOP] = 0b1_1111;
+            rows[ASCII_TOP + 1..ASCII_TOP + 6].fill(0b1_0001);
+            rows[ASCII_TOP + 6] = 0b1_1111;
+        }
+    }
+    rows
+}
+
+/// Two columns of four 2x2 dots, laid out by the braille dot numbering.
+fn braille(pattern: u32, rows: &mut [u8; CELL_HEIGHT]) {
+    const DOT_BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+    for (col, bits) in DOT_BITS.iter().enumerate() {
+        for (row, bit) in bits.iter().enumerate() {
+            if pattern & bit != 0 {
+                let y = row * 3;
+                rows[y] |= 0b11 << (col * 3);
+                rows[y + 1] |= 0b11 << (col * 3);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_glyphs() {
+        let h = glyph('H');
+        assert_eq!(h[..ASCII_TOP], [0, 0]);
+        assert_eq!(h[ASCII_TOP], 0b1_0001);
+        assert_eq!(h[ASCII_TOP + 3], 0b1_1111);
+        assert_eq!(h[ASCII_TOP + 7..], [0, 0, 0]);
+        assert_eq!(glyph(' '), [0; CELL_HEIGHT]);
+        assert_eq!(glyph('_')[ASCII_TOP + 6], 0b1_1111);
+    }
+
+    #[test]
+    fn test_unicode_glyphs() {
+        assert_eq!(glyph('█'), [FULL_ROW; CELL_HEIGHT]);
+        assert_eq!(glyph('▁').iter().filter(|row| **row != 0).count(), 2);
+        assert_eq!(
+            glyph('⣿').iter().filter(|row| **row == 0b01_1011).count(),
+            8
+        );
+        let dot = glyph('⠁');
+        assert_eq!(dot[..2], [0b11, 0b11]);
+        assert!(dot[2..].iter().all(|row| *row == 0));
+    }
+
+    #[test]
+    fn test_unknown_glyph_is_a_box() {
+        assert_eq!(glyph('東'), glyph('☀'));
+        assert_ne!(glyph('東'), [0; CELL_HEIGHT]);
+    }
+}
@@ -0,0 +1,317 @@
+use super::font::{self, CELL_HEIGHT, CELL_WIDTH};
+use super::{DEFAULT_BG, DEFAULT_FG};
+use crate::render::{TerminalRenderer, ansi_value_rgb, color_rgb, to_ansi256};
+use crossterm::style::Color;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// Screen pixels per font pixel.
+const SCALE: usize = 2;
+
+/// Codes are 8-bit palette indices, so LZW starts from 9-bit codes.
+const MIN_CODE_SIZE: u8 = 8;
+const MAX_CODES: u16 = 4096;
+
+/// Writes frames as an endlessly looping GIF89a in the xterm 256-color
+/// palette. After the first frame only the rectangle that changed is stored.
+pub struct GifWriter<W: Write> {
+    out: W,
+    columns: usize,
+    rows: usize,
+    previous: Option<Vec<u8>>,
+}
+
+impl<W: Write> GifWriter<W> {
+    pub fn new(mut out: W, columns: u16, rows: u16) -> io::Result<Self> {
+        let columns = columns as usize;
+        let rows = rows as usize;
+        let width = (columns * CELL_WIDTH * SCALE) as u16;
+        let height = (rows * CELL_HEIGHT * SCALE) as u16;
+
+        out.write_all(b"GIF89a")?;
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        // Global color table of 256 entries, 8 bits per channel
+        out.write_all(&[0xF7, 0, 0])?;
+        for index in 0..=255 {
+            let (r, g, b) = ansi_value_rgb(index);
+            out.write_all(&[r, g, b])?;
+        }
+        // Loop forever
+        out.write_all(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+        Ok(Self {
+            out,
+            columns,
+            rows,
+            previous: None,
+        })
+    }
+
+    /// Adds the renderer's current frame, shown for `delay_cs` hundredths of a second.
+    pub fn frame(&mut self, renderer: &TerminalRenderer, delay_cs: u16) -> io::Result<()> {
+        let pixels = self.rasterize(renderer);
+        let width = self.columns * CELL_WIDTH * SCALE;
+        let height = self.rows * CELL_HEIGHT * SCALE;
+        let (left, top, right, bottom) = match self.previous {
+            Some(ref previous) => changed_area(previous, &pixels, width).unwrap_or((0, 0, 1, 1)),
+            None => (0, 0, width, height),
+        };
+
+        // Graphic control: keep this frame under the next one, no transparency
+        self.out.write_all(&[0x21, 0xF9, 0x04, 0x04])?;
+        self.out.write_all(&delay_cs.to_le_bytes())?;
+        self.out.write_all(&[0, 0])?;
+
+        self.out.write_all(&[0x2C])?;
+        for value in [left, top, right - left, bottom - top] {
+            self.out.write_all(&(value as u16).to_le_bytes())?;
+        }
+        self.out.write_all(&[0])?;
+
+        let area: Vec<u8> = (top..bottom)
+            .flat_map(|y| &pixels[y * width + left..y * width + right])
+            .copied()
+            .collect();
+        self.out.write_all(&[MIN_CODE_SIZE])?;
+        for block in lzw_encode(&area).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0])?;
+
+        self.previous = Some(pixels);
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.write_all(&[0x3B])?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    /// Palette indices for every pixel of the frame, row by row.
+    fn rasterize(&self, renderer: &TerminalRenderer) -> Vec<u8> {
+        let width = self.columns * CELL_WIDTH * SCALE;
+        let mut pixels = vec![0; width * self.rows * CELL_HEIGHT * SCALE];
+
+        for row in 0..self.rows {
+            for col in 0..self.columns {
+                let Some(cell) = renderer.cell(col as u16, row as u16) else {
+                    continue;
+                };
+                let fg = palette_index(cell.fg(), DEFAULT_FG);
+                let bg = palette_index(cell.bg(), DEFAULT_BG);
+                let glyph = cell
+                    .symbol()
+                    .chars()
+                    .next()
+                    .map_or([0; CELL_HEIGHT], font::glyph);
+
+                for (gy, bits) in glyph.iter().enumerate() {
+                    for gx in 0..CELL_WIDTH {
+                        let index = if bits >> gx & 1 == 1 { fg } else { bg };
+                        let x = (col * CELL_WIDTH + gx) * SCALE;
+                        let y = (row * CELL_HEIGHT + gy) * SCALE;
+                        for sy in 0..SCALE {
+                            let start = (y + sy) * width + x;
+                            pixels[start..start + SCALE].fill(index);
+                        }
+                    }
+                }
+            }
+        }
+        pixels
+    }
+}
+
+fn palette_index(color: Color, default: (u8, u8, u8)) -> u8 {
+    let (r, g, b) = color_rgb(color).unwrap_or(default);
+    to_ansi256(r, g, b)
+}
+
+/// The smallest (left, top, right, bottom) rectangle holding every changed
+/// pixel, or `None` if the frames are the same.
+fn changed_area(
+    previous: &[u8],
+    pixels: &[u8],
+    width: usize,
+) -> Option<(usize, usize, usize, usize)> {
+    let mut area: Option<(usize, usize, usize, usize)> = None;
+    for (y, (old, new)) in previous.chunks(width).zip(pixels.chunks(width)).enumerate() {
+        let Some(first) = old.iter().zip(new).position(|(a, b)| a != b) else {
+            continue;
+        };
+        let last = old
+            .iter()
+            .zip(new)
+            .rposition(|(a, b)| a != b)
+            .unwrap_or(first);
+        area = Some(match area {
+            Some((left, top, right, _)) => (left.min(first), top, right.max(last + 1), y + 1),
+            None => (first, y, last + 1, y + 1),
+        });
+    }
+    area
+}
+
+/// Variable-width LZW as GIF uses it: codes start one bit wider than the
+/// palette indices and grow to 12 bits, and the table is reset when full.
+fn lzw_encode(indices: &[u8]) -> Vec<u8> {
+    let clear: u16 = 1 << MIN_CODE_SIZE;
+    let end = clear + 1;
+
+    let mut output = Vec::new();
+    let mut bits: u32 = 0;
+    let mut bit_count = 0;
+    let mut emit = |code: u16, size: u8, output: &mut Vec<u8>| {
+        bits |= (code as u32) << bit_count;
+        bit_count += size;
+        while bit_count >= 8 {
+            output.push(bits as u8);
+            bits >>= 8;
+            bit_count -= 8;
+        }
+    };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = MIN_CODE_SIZE + 1;
+    let mut next_code = end + 1;
+    emit(clear, code_size, &mut output);
+
+    let mut prefix: Option<u16> = None;
+    for &index in indices {
+        let Some(current) = prefix else {
+            prefix = Some(index as u16);
+            continue;
+        };
+        if let Some(&code) = table.get(&(current, index)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        emit(current, code_size, &mut output);
+        if next_code < MAX_CODES {
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            table.insert((current, index), next_code);
+            next_code += 1;
+        } else {
+            emit(clear, code_size, &mut output);
+            table.clear();
+            code_size = MIN_CODE_SIZE + 1;
+            next_code = end + 1;
+        }
+        prefix = Some(index as u16);
+    }
+
+    if let Some(current) = prefix {
+        emit(current, code_size, &mut output);
+    }
+    emit(end, code_size, &mut output);
+    if bit_count > 0 {
+        output.push(bits as u8);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plain GIF LZW decoder, to check the encoder against.
+    fn lzw_decode(data: &[u8]) -> Vec<u8> {
+        let clear = 1usize << MIN_CODE_SIZE;
+        let end = clear + 1;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            table.clear();
+            table.extend((0..clear).map(|i| vec![i as u8]));
+            table.extend([Vec::new(), Vec::new()]);
+        };
+        reset(&mut table);
+
+        let mut output = Vec::new();
+        let mut code_size = MIN_CODE_SIZE as usize + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut bit_pos = 0;
+        while bit_pos + code_size <= data.len() * 8 {
+            let mut code = 0;
+            for i in 0..code_size {
+                let bit = (data[(bit_pos + i) / 8] >> ((bit_pos + i) % 8)) & 1;
+                code |= (bit as usize) << i;
+            }
+            bit_pos += code_size;
+
+            if code == clear {
+                reset(&mut table);
+                code_size = MIN_CODE_SIZE as usize + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = prev.clone();
+                    entry.push(prev[0]);
+                    entry
+                }
+                (None, None) => panic!("code {code} before any output"),
+            };
+            if let Some(mut prev) = previous.take() {
+                prev.push(entry[0]);
+                table.push(prev);
+            }
+            if table.len() == 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+        output
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let short = vec![7, 7, 7, 7, 3, 7, 7, 3, 3, 3];
+        assert_eq!(lzw_decode(&lzw_encode(&short)), short);
+
+        // Long and varied enough to fill the table and reset it
+        let long: Vec<u8> = (0..200_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 26) as u8)
+            .collect();
+        assert_eq!(lzw_decode(&lzw_encode(&long)), long);
+    }
+
+    #[test]
+    fn test_changed_area() {
+        let old = vec![0; 12];
+        let mut new = old.clone();
+        assert_eq!(changed_area(&old, &new, 4), None);
+        new[5] = 1;
+        new[10] = 1;
+        assert_eq!(changed_area(&old, &new, 4), Some((1, 1, 3, 3)));
+    }
+
+    #[test]
+    fn test_gif_structure() {
+        let mut renderer = TerminalRenderer::headless(4, 2);
+        renderer
+            .render_line_colored(0, 0, "Hi", Color::Red)
+            .unwrap();
+        let mut gif = GifWriter::new(Vec::new(), 4, 2).unwrap();
+        gif.frame(&renderer, 10).unwrap();
+        renderer.render_line_colored(0, 1, "!", Color::Red).unwrap();
+        gif.frame(&renderer, 10).unwrap();
+        let bytes = gif.finish().unwrap();
+
+        assert_eq!(&bytes[..6], b"GIF89a");
+        assert_eq!(u16::from_le_bytes([bytes[6], bytes[7]]), 48);
+        assert_eq!(u16::from_le_bytes([bytes[8], bytes[9]]), 48);
+        assert_eq!(bytes.last(), Some(&0x3B));
+    }
+}
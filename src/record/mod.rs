@@ -0,0 +1,173 @@
+//! Saving off-screen frames to files: asciicast for terminal players, and
+//! animated SVG or GIF for everywhere else.
+
+mod asciicast;
+mod font;
+mod gif;
+mod svg;
+
+use crate::error::RecordError;
+use crate::render::TerminalRenderer;
+use asciicast::AsciicastWriter;
+use gif::GifWriter;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+use svg::SvgWriter;
+
+/// Colors for cells left at the terminal's default, as a dark terminal shows them.
+const DEFAULT_FG: (u8, u8, u8) = (204, 204, 204);
+const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    Asciicast,
+    Svg,
+    Gif,
+}
+
+impl RecordFormat {
+    /// The format named by the file extension: `.cast`, `.svg` or `.gif`.
+    pub fn from_path(path: &Path) -> Result<Self, RecordError> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("cast") => Ok(RecordFormat::Asciicast),
+            Some("svg") => Ok(RecordFormat::Svg),
+            Some("gif") => Ok(RecordFormat::Gif),
+            _ => Err(RecordError::UnsupportedFormat(path.display().to_string())),
+        }
+    }
+}
+
+enum Encoder<W: Write> {
+    Asciicast(AsciicastWriter<W>),
+    Svg(SvgWriter<W>),
+    Gif(GifWriter<W>),
+}
+
+/// Keeps frames from a headless renderer at a fixed rate, however often the
+/// scene itself is drawn, and encodes them into `W`.
+pub struct Recorder<W: Write> {
+    encoder: Encoder<W>,
+    fps: u32,
+    frames: u32,
+    /// Terminal output since the last kept frame, for asciicast.
+    pending: Vec<u8>,
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(
+        out: W,
+        format: RecordFormat,
+        columns: u16,
+        rows: u16,
+        fps: u32,
+    ) -> io::Result<Self> {
+        let encoder = match format {
+            RecordFormat::Asciicast => {
+                Encoder::Asciicast(AsciicastWriter::new(out, columns, rows)?)
+            }
+            RecordFormat::Svg => Encoder::Svg(SvgWriter::new(out, columns, rows)),
+            RecordFormat::Gif => Encoder::Gif(GifWriter::new(out, columns, rows)?),
+        };
+        Ok(Self {
+            encoder,
+            fps: fps.max(1),
+            frames: 0,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Offers the frame the renderer last flushed, drawn `time` into the
+    /// recording. It is kept if a frame is due by then.
+    pub fn capture(&mut self, renderer: &mut TerminalRenderer, time: Duration) -> io::Result<()> {
+        self.pending.extend(renderer.take_output());
+        if time < self.frame_time(self.frames) {
+            return Ok(());
+        }
+
+        let delay = self.frame_time(self.frames + 1) - self.frame_time(self.frames);
+        match self.encoder {
+            Encoder::Asciicast(ref mut cast) => cast.frame(time, &self.pending)?,
+            Encoder::Svg(ref mut svg) => svg.frame(renderer),
+            Encoder::Gif(ref mut gif) => gif.frame(renderer, (delay.as_millis() / 10) as u16)?,
+        }
+        self.pending.clear();
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Number of frames kept so far.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Completes the file and hands back the writer.
+    pub fn finish(self) -> io::Result<W> {
+        match self.encoder {
+            Encoder::Asciicast(cast) => cast.finish(),
+            Encoder::Svg(svg) => svg.finish(1.0 / self.fps as f32),
+            Encoder::Gif(gif) => gif.finish(),
+        }
+    }
+
+    /// When frame `index` is due. GIF delays are whole hundredths, so frame
+    /// times are rounded to them and the rounding does not add up over time.
+    fn frame_time(&self, index: u32) -> Duration {
+        Duration::from_millis((index as u64 * 100).div_ceil(self.fps as u64) * 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::style::Color;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            RecordFormat::from_path(Path::new("rain.cast")).unwrap(),
+            RecordFormat::Asciicast
+        );
+        assert_eq!(
+            RecordFormat::from_path(Path::new("out/Snow.GIF")).unwrap(),
+            RecordFormat::Gif
+        );
+        assert_eq!(
+            RecordFormat::from_path(Path::new("a.svg")).unwrap(),
+            RecordFormat::Svg
+        );
+        assert!(RecordFormat::from_path(Path::new("clip.mp4")).is_err());
+        assert!(RecordFormat::from_path(Path::new("clip")).is_err());
+    }
+
+    #[test]
+    fn test_recorder_keeps_frames_at_its_rate() {
+        let mut renderer = TerminalRenderer::headless(8, 2);
+        let mut recorder = Recorder::new(Vec::new(), RecordFormat::Asciicast, 8, 2, 10).unwrap();
+
+        // Drawn at 30 fps for one second, kept at 10
+        for frame in 0..30u32 {
+            renderer.clear().unwrap();
+            renderer
+                .render_line_colored(0, 0, &frame.to_string(), Color::White)
+                .unwrap();
+            renderer.flush().unwrap();
+            recorder
+                .capture(
+                    &mut renderer,
+                    Duration::from_millis(frame as u64 * 1000 / 30),
+                )
+                .unwrap();
+        }
+        assert_eq!(recorder.frames(), 10);
+
+        let cast = String::from_utf8(recorder.finish().unwrap()).unwrap();
+        assert_eq!(cast.lines().count(), 11);
+        // Output of skipped frames is carried into the next kept one
+        assert!(cast.lines().nth(2).unwrap().contains('2'));
+    }
+}
@@ -0,0 +1,179 @@
+use super::{DEFAULT_BG, DEFAULT_FG};
+use crate::render::{Cell, TerminalRenderer, color_rgb};
+use crossterm::style::Color;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+/// Cell size in SVG units for a 14px monospace font.
+const CELL_WIDTH: f32 = 8.4;
+const CELL_HEIGHT: f32 = 17.0;
+const FONT_SIZE: f32 = 14.0;
+/// Distance from the top of a cell to the text baseline.
+const BASELINE: f32 = 13.0;
+
+/// Writes frames as an animated SVG. The frames are laid side by side on one
+/// strip, and a CSS animation steps the strip through the viewport, so the
+/// file plays in any browser without scripts.
+pub struct SvgWriter<W: Write> {
+    out: W,
+    columns: u16,
+    rows: u16,
+    frames: Vec<String>,
+}
+
+impl<W: Write> SvgWriter<W> {
+    pub fn new(out: W, columns: u16, rows: u16) -> Self {
+        Self {
+            out,
+            columns,
+            rows,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Adds the renderer's current frame.
+    pub fn frame(&mut self, renderer: &TerminalRenderer) {
+        let offset = self.frames.len() as f32 * self.width();
+        let mut markup = format!("<g transform=\"translate({offset:.1},0)\">");
+
+        for row in 0..self.rows {
+            let y = row as f32 * CELL_HEIGHT;
+            for (start, end, color) in runs(renderer, row, self.columns, |cell| cell.bg()) {
+                if color == Color::Reset {
+                    continue;
+                }
+                let _ = write!(
+                    markup,
+                    "<rect x=\"{:.1}\" y=\"{y:.1}\" width=\"{:.1}\" height=\"{CELL_HEIGHT:.1}\" fill=\"{}\"/>",
+                    start as f32 * CELL_WIDTH,
+                    (end - start) as f32 * CELL_WIDTH,
+                    css_color(color, DEFAULT_BG),
+                );
+            }
+            for (start, end, color) in runs(renderer, row, self.columns, |cell| cell.fg()) {
+                let text: String = (start..end)
+                    .filter_map(|col| renderer.cell(col, row))
+                    .map(|cell| cell.symbol())
+                    .collect();
+                let trimmed = text.trim_start();
+                if trimmed.trim_end().is_empty() {
+                    continue;
+                }
+                let leading = text.len() - trimmed.len();
+                let _ = write!(
+                    markup,
+                    "<text x=\"{:.1}\" y=\"{:.1}\" fill=\"{}\">{}</text>",
+                    (start as usize + leading) as f32 * CELL_WIDTH,
+                    y + BASELINE,
+                    css_color(color, DEFAULT_FG),
+                    escape(trimmed.trim_end()),
+                );
+            }
+        }
+
+        markup.push_str("</g>");
+        self.frames.push(markup);
+    }
+
+    /// Writes the document, showing each frame for `frame_secs`.
+    pub fn finish(mut self, frame_secs: f32) -> io::Result<W> {
+        let width = self.width();
+        let height = self.rows as f32 * CELL_HEIGHT;
+        let count = self.frames.len().max(1);
+        let (r, g, b) = DEFAULT_BG;
+
+        writeln!(
+            self.out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.1}\" height=\"{height:.1}\" viewBox=\"0 0 {width:.1} {height:.1}\">"
+        )?;
+        writeln!(
+            self.out,
+            "<style>\
+             @keyframes play {{ to {{ transform: translateX({:.1}px); }} }}\
+             .reel {{ animation: play {:.3}s steps({count}) infinite; }}\
+             text {{ font-family: ui-monospace, Menlo, Consolas, monospace; font-size: {FONT_SIZE}px; white-space: pre; }}\
+             </style>",
+            -(count as f32) * width,
+            count as f32 * frame_secs,
+        )?;
+        writeln!(
+            self.out,
+            "<rect width=\"100%\" height=\"100%\" fill=\"#{r:02x}{g:02x}{b:02x}\"/>"
+        )?;
+        writeln!(self.out, "<g class=\"reel\">")?;
+        for frame in &self.frames {
+            writeln!(self.out, "{frame}")?;
+        }
+        writeln!(self.out, "</g>\n</svg>")?;
+        self.out.flush()?;
+        Ok(self.out)
+    }
+
+    fn width(&self) -> f32 {
+        self.columns as f32 * CELL_WIDTH
+    }
+}
+
+/// Spans of `row` whose cells share a color, as (start, end, color). A span
+/// also ends after a double-width symbol, whose width the font may not match.
+fn runs(
+    renderer: &TerminalRenderer,
+    row: u16,
+    columns: u16,
+    color_of: impl Fn(&Cell) -> Color,
+) -> Vec<(u16, u16, Color)> {
+    let mut runs: Vec<(u16, u16, Color)> = Vec::new();
+    let mut split = false;
+    for col in 0..columns {
+        let Some(cell) = renderer.cell(col, row) else {
+            break;
+        };
+        let color = color_of(cell);
+        match runs.last_mut() {
+            Some(run) if run.2 == color && !split => run.1 = col + 1,
+            _ => runs.push((col, col + 1, color)),
+        }
+        split = cell.symbol().is_empty();
+    }
+    runs
+}
+
+fn css_color(color: Color, default: (u8, u8, u8)) -> String {
+    let (r, g, b) = color_rgb(color).unwrap_or(default);
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_frames_and_text() {
+        let mut renderer = TerminalRenderer::headless(10, 2);
+        renderer
+            .render_line_colored(2, 0, "<a&b>", Color::Rgb { r: 255, g: 0, b: 0 })
+            .unwrap();
+        renderer.fill_background(1, Color::DarkBlue).unwrap();
+
+        let mut svg = SvgWriter::new(Vec::new(), 10, 2);
+        svg.frame(&renderer);
+        svg.frame(&renderer);
+        let doc = String::from_utf8(svg.finish(0.5).unwrap()).unwrap();
+
+        assert!(doc.starts_with("<svg"));
+        assert!(doc.contains("steps(2)"));
+        assert!(doc.contains("animation: play 1.000s"));
+        assert!(doc.contains("translateX(-168.0px)"));
+        assert!(
+            doc.contains("<text x=\"16.8\" y=\"13.0\" fill=\"#ff0000\">&lt;a&amp;b&gt;</text>")
+        );
+        assert!(doc.contains("<g transform=\"translate(84.0,0)\">"));
+        assert!(doc.contains("fill=\"#000080\""));
+    }
+}
@@ -1,3 +1,4 @@
+use super::palette::{ansi_value_rgb, to_ansi8, to_ansi16, to_ansi256};
 use crossterm::style::Color;
 use std::env;
 use std::io::IsTerminal;
@@ -15,16 +16,24 @@ pub struct TerminalCapabilities {
     pub color_support: ColorSupport,
     #[allow(dead_code)]
     pub is_tty: bool,
+    /// Whether the locale says the terminal takes UTF-8, so non-ASCII glyphs are safe.
+    pub utf8: bool,
 }
 
 impl TerminalCapabilities {
     pub fn detect() -> Self {
         let is_tty = std::io::stdout().is_terminal();
+        let utf8 = is_utf8_locale(
+            env::var("LC_ALL").ok().as_deref(),
+            env::var("LC_CTYPE").ok().as_deref(),
+            env::var("LANG").ok().as_deref(),
+        );
 
         if env::var("NO_COLOR").is_ok() {
             return Self {
                 color_support: ColorSupport::None,
                 is_tty,
+                utf8,
             };
         }
 
@@ -32,6 +41,7 @@ impl TerminalCapabilities {
             return Self {
                 color_support: ColorSupport::None,
                 is_tty,
+                utf8,
             };
         }
 
@@ -39,6 +49,7 @@ impl TerminalCapabilities {
             return Self {
                 color_support: ColorSupport::None,
                 is_tty,
+                utf8,
             };
         }
 
@@ -55,24 +66,63 @@ impl TerminalCapabilities {
         Self {
             color_support,
             is_tty,
+            utf8,
         }
     }
 
-    pub fn adjust_color(&self, color: Color) -> Color {
-        if self.color_support == ColorSupport::None {
-            return Color::Reset;
+    /// What a renderer without a terminal assumes: every color and UTF-8.
+    pub fn headless() -> Self {
+        Self {
+            color_support: ColorSupport::TrueColor,
+            is_tty: false,
+            utf8: true,
         }
+    }
 
+    /// Maps a color to the nearest one the terminal can show.
+    pub fn adjust_color(&self, color: Color) -> Color {
         match self.color_support {
             ColorSupport::None => Color::Reset,
             ColorSupport::Basic => match color {
-                Color::Rgb { .. } => Color::White,
+                Color::Rgb { r, g, b } => to_ansi16(r, g, b),
+                Color::AnsiValue(index) => {
+                    let (r, g, b) = ansi_value_rgb(index);
+                    to_ansi16(r, g, b)
+                }
+                _ => color,
+            },
+            ColorSupport::Ansi256 => match color {
+                Color::Rgb { r, g, b } => Color::AnsiValue(to_ansi256(r, g, b)),
                 _ => color,
             },
-            ColorSupport::Ansi256 => color,
             ColorSupport::TrueColor => color,
         }
     }
+
+    /// Like `adjust_color`, but basic terminals only get the 8 colors that
+    /// every one of them can show as a background.
+    pub fn adjust_background(&self, color: Color) -> Color {
+        match (self.color_support, color) {
+            (ColorSupport::Basic, Color::Rgb { r, g, b }) => to_ansi8(r, g, b),
+            (ColorSupport::Basic, Color::AnsiValue(index)) => {
+                let (r, g, b) = ansi_value_rgb(index);
+                to_ansi8(r, g, b)
+            }
+            _ => self.adjust_color(color),
+        }
+    }
+}
+
+/// The first locale variable that is set decides, as with the C library.
+fn is_utf8_locale(lc_all: Option<&str>, lc_ctype: Option<&str>, lang: Option<&str>) -> bool {
+    [lc_all, lc_ctype, lang]
+        .into_iter()
+        .flatten()
+        .find(|value| !value.is_empty())
+        .is_some_and(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
 }
 
 fn check_term_for_256() -> ColorSupport {
@@ -92,6 +142,7 @@ mod tests {
         let caps = TerminalCapabilities {
             color_support: ColorSupport::None,
             is_tty: true,
+            utf8: false,
         };
         assert_eq!(caps.adjust_color(Color::Red), Color::Reset);
         assert_eq!(
@@ -105,11 +156,40 @@ mod tests {
         let caps = TerminalCapabilities {
             color_support: ColorSupport::Basic,
             is_tty: true,
+            utf8: false,
         };
         assert_eq!(caps.adjust_color(Color::Red), Color::Red);
         assert_eq!(
             caps.adjust_color(Color::Rgb { r: 255, g: 0, b: 0 }),
-            Color::White
+            Color::Red
+        );
+        assert_eq!(
+            caps.adjust_color(Color::Rgb {
+                r: 139,
+                g: 69,
+                b: 19
+            }),
+            Color::DarkYellow
+        );
+        assert_eq!(caps.adjust_color(Color::AnsiValue(21)), Color::Blue);
+    }
+
+    #[test]
+    fn test_adjust_background_basic() {
+        let caps = TerminalCapabilities {
+            color_support: ColorSupport::Basic,
+            is_tty: true,
+            utf8: false,
+        };
+        let sky = Color::Rgb {
+            r: 30,
+            g: 90,
+            b: 200,
+        };
+        assert_eq!(caps.adjust_background(sky), Color::DarkCyan);
+        assert_eq!(
+            caps.adjust_background(Color::AnsiValue(21)),
+            Color::DarkBlue
         );
     }
 
@@ -118,10 +198,11 @@ mod tests {
         let caps = TerminalCapabilities {
             color_support: ColorSupport::Ansi256,
             is_tty: true,
+            utf8: false,
         };
         assert_eq!(caps.adjust_color(Color::Red), Color::Red);
         let rgb = Color::Rgb { r: 255, g: 0, b: 0 };
-        assert_eq!(caps.adjust_color(rgb), rgb);
+        assert_eq!(caps.adjust_color(rgb), Color::AnsiValue(196));
     }
 
     #[test]
@@ -129,9 +210,19 @@ mod tests {
         let caps = TerminalCapabilities {
             color_support: ColorSupport::TrueColor,
             is_tty: true,
+            utf8: false,
         };
         assert_eq!(caps.adjust_color(Color::Red), Color::Red);
         let rgb = Color::Rgb { r: 255, g: 0, b: 0 };
         assert_eq!(caps.adjust_color(rgb), rgb);
     }
+
+    #[test]
+    fn test_utf8_locale() {
+        assert!(is_utf8_locale(None, None, Some("en_US.UTF-8")));
+        assert!(is_utf8_locale(None, Some("C.utf8"), Some("C")));
+        assert!(!is_utf8_locale(Some("C"), None, Some("en_US.UTF-8")));
+        assert!(is_utf8_locale(Some(""), None, Some("de_DE.UTF-8")));
+        assert!(!is_utf8_locale(None, None, None));
+    }
 }
@@ -0,0 +1,59 @@
+/// First code point of the Unicode braille block; the low 8 bits pick the dots.
+const BRAILLE_BLANK: u32 = 0x2800;
+
+/// Braille dot bits by column, then row, within a 2x4 cell.
+const BRAILLE_DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// Dots across and down a braille cell.
+pub const DOTS_X: f32 = 2.0;
+pub const DOTS_Y: f32 = 4.0;
+
+/// The braille character `ch` with the dot at (`col`, `row`) raised. Any other
+/// character is replaced by a cell holding just that dot.
+pub fn add_braille_dot(ch: char, col: usize, row: usize) -> char {
+    let bit = BRAILLE_DOTS[col.min(1)][row.min(3)] as u32;
+    let dots = match ch as u32 {
+        code @ BRAILLE_BLANK..=0x28FF => code - BRAILLE_BLANK,
+        _ => 0,
+    };
+    char::from_u32(BRAILLE_BLANK | dots | bit).unwrap_or(ch)
+}
+
+/// Box-drawing equivalent of an ASCII line character.
+pub fn box_drawing(ch: char) -> char {
+    match ch {
+        '|' => '│',
+        '-' => '─',
+        '=' => '═',
+        '+' => '┼',
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_braille_dots_combine() {
+        assert_eq!(add_braille_dot(' ', 0, 0), '⠁');
+        assert_eq!(add_braille_dot('⠁', 1, 3), '⢁');
+        assert_eq!(add_braille_dot('⢁', 1, 3), '⢁');
+        assert_eq!(add_braille_dot('*', 0, 3), '⡀');
+
+        let mut full = ' ';
+        for col in 0..2 {
+            for row in 0..4 {
+                full = add_braille_dot(full, col, row);
+            }
+        }
+        assert_eq!(full, '⣿');
+    }
+
+    #[test]
+    fn test_box_drawing() {
+        assert_eq!(box_drawing('|'), '│');
+        assert_eq!(box_drawing('='), '═');
+        assert_eq!(box_drawing('/'), '/');
+    }
+}
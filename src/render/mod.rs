@@ -1,28 +1,56 @@
 mod capabilities;
+mod glyphs;
+mod palette;
+mod target;
+mod text;
 
 use crate::error::TerminalError;
+use crate::theme::{Palette, Theme};
 use capabilities::TerminalCapabilities;
 use crossterm::{
     cursor, execute, queue,
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{self, Clear, ClearType},
 };
-use std::io::{self, BufWriter, IsTerminal, Stdout, Write};
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Write};
+use target::{HeadlessTarget, RenderTarget, TerminalTarget};
+use text::{Symbol, grapheme_width};
+use unicode_segmentation::UnicodeSegmentation;
 
-const MIN_TERMINAL_WIDTH: u16 = 70;
-const MIN_TERMINAL_HEIGHT: u16 = 20;
+pub use palette::{ansi_value_rgb, color_rgb, to_ansi256};
+pub use text::{char_width, display_width};
+
+/// Smallest size the compact scene layout fits in.
+pub const MIN_TERMINAL_WIDTH: u16 = 30;
+pub const MIN_TERMINAL_HEIGHT: u16 = 12;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-struct Cell {
-    character: char,
+pub struct Cell {
+    symbol: Symbol,
     color: Color,
     bg_color: Color,
 }
 
+impl Cell {
+    /// The grapheme shown, or "" for the right half of a double-width one.
+    pub fn symbol(&self) -> &str {
+        self.symbol.as_str()
+    }
+
+    pub fn fg(&self) -> Color {
+        self.color
+    }
+
+    pub fn bg(&self) -> Color {
+        self.bg_color
+    }
+}
+
 impl Default for Cell {
     fn default() -> Self {
         Self {
-            character: ' ',
+            symbol: Symbol::from_char(' '),
             color: Color::Reset,
             bg_color: Color::Reset,
         }
@@ -30,12 +58,14 @@ impl Default for Cell {
 }
 
 pub struct TerminalRenderer {
-    stdout: BufWriter<Stdout>,
+    target: Box<dyn RenderTarget>,
     width: u16,
     height: u16,
     buffer: Vec<Cell>,
     last_buffer: Vec<Cell>,
     capabilities: TerminalCapabilities,
+    theme: Theme,
+    unicode: bool,
 }
 
 impl TerminalRenderer {
@@ -55,31 +85,85 @@ impl TerminalRenderer {
             });
         }
 
-        let stdout = BufWriter::new(io::stdout());
-        let buffer_size = (width as usize) * (height as usize);
-        let capabilities = TerminalCapabilities::detect();
+        Ok(Self::with_target(
+            Box::new(TerminalTarget::new()),
+            width,
+            height,
+            TerminalCapabilities::detect(),
+        ))
+    }
 
-        Ok(Self {
-            stdout,
+    /// A renderer of the given size that draws into its cell buffer only, with
+    /// truecolor and UTF-8 assumed. For tests and for rendering to files.
+    pub fn headless(width: u16, height: u16) -> Self {
+        Self::with_target(
+            Box::new(HeadlessTarget::default()),
+            width,
+            height,
+            TerminalCapabilities::headless(),
+        )
+    }
+
+    fn with_target(
+        target: Box<dyn RenderTarget>,
+        width: u16,
+        height: u16,
+        capabilities: TerminalCapabilities,
+    ) -> Self {
+        let buffer_size = (width as usize) * (height as usize);
+        Self {
+            target,
             width,
             height,
             buffer: vec![Cell::default(); buffer_size],
             last_buffer: vec![Cell::default(); buffer_size],
             capabilities,
-        })
+            theme: Theme::default(),
+            unicode: false,
+        }
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// The theme's colors for day or night.
+    pub fn palette(&self, is_day: bool) -> Palette {
+        *self.theme.palette(is_day)
+    }
+
+    /// Turns on Unicode glyphs if the locale is UTF-8, and reports whether it did.
+    pub fn set_unicode(&mut self, requested: bool) -> bool {
+        self.unicode = requested && self.capabilities.utf8;
+        self.unicode
+    }
+
+    /// Whether the locale says non-ASCII text is safe to print, whether or not
+    /// Unicode drawing mode is on.
+    pub fn supports_utf8(&self) -> bool {
+        self.capabilities.utf8
+    }
+
+    /// Whether to draw with Unicode blocks and braille rather than plain ASCII.
+    pub fn is_unicode(&self) -> bool {
+        self.unicode
+    }
+
+    /// Box-drawing version of an ASCII line character in Unicode mode.
+    pub fn line_char(&self, ch: char) -> char {
+        if self.unicode {
+            glyphs::box_drawing(ch)
+        } else {
+            ch
+        }
     }
 
     pub fn init(&mut self) -> Result<(), TerminalError> {
-        terminal::enable_raw_mode().map_err(TerminalError::RawModeError)?;
-        execute!(self.stdout, EnterAlternateScreen, cursor::Hide)
-            .map_err(TerminalError::InitError)?;
-        Ok(())
+        self.target.enter()
     }
 
     pub fn cleanup(&mut self) -> io::Result<()> {
-        execute!(self.stdout, LeaveAlternateScreen, cursor::Show, ResetColor)?;
-        terminal::disable_raw_mode()?;
-        Ok(())
+        self.target.leave()
     }
 
     pub fn manual_resize(&mut self, width: u16, height: u16) -> io::Result<()> {
@@ -89,7 +173,7 @@ impl TerminalRenderer {
             let buffer_size = (width as usize) * (height as usize);
             self.buffer = vec![Cell::default(); buffer_size];
             self.last_buffer = vec![Cell::default(); buffer_size];
-            execute!(self.stdout, Clear(ClearType::All))?;
+            execute!(self.target, Clear(ClearType::All))?;
         }
         Ok(())
     }
@@ -109,36 +193,18 @@ impl TerminalRenderer {
         start_row: u16,
         color: Color,
     ) -> io::Result<()> {
-        let max_width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
-        let start_col = if self.width as usize > max_width {
-            (self.width as usize - max_width) / 2
-        } else {
-            0
-        };
-        let adjusted_color = self.capabilities.adjust_color(color);
+        let max_width = lines.iter().map(|l| display_width(l)).max().unwrap_or(0);
+        let start_col = (self.width as usize).saturating_sub(max_width) / 2;
 
         for (idx, line) in lines.iter().enumerate() {
-            let row = start_row + idx as u16;
-            if row < self.height {
-                for (char_idx, ch) in line.chars().enumerate() {
-                    let col = start_col as u16 + char_idx as u16;
-                    if col < self.width {
-                        let buffer_idx = (row as usize) * (self.width as usize) + (col as usize);
-                        if buffer_idx < self.buffer.len() {
-                            self.buffer[buffer_idx] = Cell {
-                                character: ch,
-                                color: adjusted_color,
-                                bg_color: Color::Reset,
-                            };
-                        }
-                    }
-                }
-            }
+            self.render_line_colored(start_col as u16, start_row + idx as u16, line, color)?;
         }
 
         Ok(())
     }
 
+    /// Draws text from (`x`, `y`) one grapheme at a time, giving wide characters
+    /// two columns. Text past the right edge is cut off.
     pub fn render_line_colored(
         &mut self,
         x: u16,
@@ -151,32 +217,64 @@ impl TerminalRenderer {
         }
         let adjusted_color = self.capabilities.adjust_color(color);
 
-        for (idx, ch) in text.chars().enumerate() {
-            let col = x + idx as u16;
-            if col < self.width {
-                let buffer_idx = (y as usize) * (self.width as usize) + (col as usize);
-                if buffer_idx < self.buffer.len() {
-                    self.buffer[buffer_idx] = Cell {
-                        character: ch,
-                        color: adjusted_color,
-                        bg_color: Color::Reset,
-                    };
-                }
+        let mut col = x as usize;
+        for grapheme in text.graphemes(true) {
+            let width = grapheme_width(grapheme);
+            if width == 0 {
+                continue;
+            }
+            if col + width > self.width as usize {
+                break;
             }
+            self.put(
+                col as u16,
+                y,
+                Symbol::from_grapheme(grapheme),
+                width,
+                adjusted_color,
+                None,
+            );
+            col += width;
         }
         Ok(())
     }
 
+    /// Draws a character over whatever background the cell already has.
     pub fn render_char(&mut self, x: u16, y: u16, ch: char, color: Color) -> io::Result<()> {
-        if x < self.width && y < self.height {
-            let buffer_idx = (y as usize) * (self.width as usize) + (x as usize);
-            if buffer_idx < self.buffer.len() {
-                self.buffer[buffer_idx] = Cell {
-                    character: ch,
-                    color: self.capabilities.adjust_color(color),
-                    bg_color: Color::Reset,
-                };
-            }
+        let color = self.capabilities.adjust_color(color);
+        self.put_char(x, y, ch, color, None);
+        Ok(())
+    }
+
+    /// Raises one braille dot at (`x`, `y`), measured in cells, so that shapes
+    /// can be drawn at 2x4 resolution within each cell. Dots already in the
+    /// cell are kept.
+    pub fn plot_dot(&mut self, x: f32, y: f32, color: Color) -> io::Result<()> {
+        if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+            return Ok(());
+        }
+        let buffer_idx = (y as usize) * (self.width as usize) + (x as usize);
+        if buffer_idx < self.buffer.len() {
+            let col = (x.fract() * glyphs::DOTS_X) as usize;
+            let row = (y.fract() * glyphs::DOTS_Y) as usize;
+            let color = self.capabilities.adjust_color(color);
+            let existing = self.buffer[buffer_idx].symbol.first_char().unwrap_or(' ');
+            let ch = glyphs::add_braille_dot(existing, col, row);
+            self.put_char(x as u16, y as u16, ch, color, None);
+        }
+        Ok(())
+    }
+
+    /// Sets the background color of a whole row, keeping what is drawn on it.
+    pub fn fill_background(&mut self, y: u16, color: Color) -> io::Result<()> {
+        if y >= self.height {
+            return Ok(());
+        }
+        let bg_color = self.capabilities.adjust_background(color);
+        let start = (y as usize) * (self.width as usize);
+        let end = (start + self.width as usize).min(self.buffer.len());
+        for cell in &mut self.buffer[start.min(end)..end] {
+            cell.bg_color = bg_color;
         }
         Ok(())
     }
@@ -197,15 +295,9 @@ impl TerminalRenderer {
         ch: char,
         color: Color,
     ) -> io::Result<()> {
-        if x < self.width && y < self.height {
-            let buffer_idx = (y as usize) * (self.width as usize) + (x as usize);
-            if buffer_idx < self.buffer.len() {
-                let adjusted_color = self.capabilities.adjust_color(color);
-                self.buffer[buffer_idx].character = ch;
-                self.buffer[buffer_idx].color = adjusted_color;
-                // Preserve existing background color
-            }
-        }
+        let adjusted_color = self.capabilities.adjust_color(color);
+        // Preserve existing background color
+        self.put_char(x, y, ch, adjusted_color, None);
         Ok(())
     }
 
@@ -218,22 +310,141 @@ impl TerminalRenderer {
         fg_color: Color,
         bg_color: Color,
     ) -> io::Result<()> {
+        let fg_color = self.capabilities.adjust_color(fg_color);
+        let bg_color = self.capabilities.adjust_background(bg_color);
+        self.put_char(x, y, ch, fg_color, Some(bg_color));
+        Ok(())
+    }
+
+    fn put_char(&mut self, x: u16, y: u16, ch: char, color: Color, bg_color: Option<Color>) {
+        self.put(
+            x,
+            y,
+            Symbol::from_char(ch),
+            char_width(ch).max(1),
+            color,
+            bg_color,
+        );
+    }
+
+    /// Stores `symbol`, `width` columns wide, at (`x`, `y`) with colors that are
+    /// already adjusted, keeping the cell's background unless one is given. A
+    /// double-width symbol claims the cell to its right as a continuation, and
+    /// any double-width symbol it overlaps is blanked so no half is left behind.
+    fn put(
+        &mut self,
+        x: u16,
+        y: u16,
+        symbol: Symbol,
+        width: usize,
+        color: Color,
+        bg_color: Option<Color>,
+    ) {
+        let row_start = (y as usize) * (self.width as usize);
+        let col = x as usize;
+        if y >= self.height || col + width > self.width as usize {
+            return;
+        }
+
+        for covered in col..col + width {
+            self.split_wide(row_start, covered);
+        }
+
+        let idx = row_start + col;
+        let bg_color = bg_color.unwrap_or(self.buffer[idx].bg_color);
+        self.buffer[idx] = Cell {
+            symbol,
+            color,
+            bg_color,
+        };
+        if width == 2 {
+            self.buffer[idx + 1] = Cell {
+                symbol: Symbol::CONTINUATION,
+                color,
+                bg_color,
+            };
+        }
+    }
+
+    /// Blanks the other half of a double-width symbol at `col`, if there is one.
+    fn split_wide(&mut self, row_start: usize, col: usize) {
+        let idx = row_start + col;
+        if self.buffer[idx].symbol.is_continuation() {
+            if col > 0 {
+                self.buffer[idx - 1].symbol = Symbol::from_char(' ');
+            }
+        } else if col + 1 < self.width as usize && self.buffer[idx + 1].symbol.is_continuation() {
+            self.buffer[idx + 1].symbol = Symbol::from_char(' ');
+        }
+    }
+
+    /// The cell at (`x`, `y`) as it will be drawn on the next flush.
+    pub fn cell(&self, x: u16, y: u16) -> Option<&Cell> {
         if x < self.width && y < self.height {
-            let buffer_idx = (y as usize) * (self.width as usize) + (x as usize);
-            if buffer_idx < self.buffer.len() {
-                self.buffer[buffer_idx] = Cell {
-                    character: ch,
-                    color: self.capabilities.adjust_color(fg_color),
-                    bg_color: self.capabilities.adjust_color(bg_color),
-                };
+            self.buffer
+                .get((y as usize) * (self.width as usize) + (x as usize))
+        } else {
+            None
+        }
+    }
+
+    /// The characters of the current frame, one line per row, without colors
+    /// and with trailing blanks trimmed.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for row in self.buffer.chunks(self.width.max(1) as usize) {
+            let line: String = row.iter().map(|cell| cell.symbol()).collect();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The current frame with color escapes, one line per row, for files and
+    /// pipes rather than the screen: no cursor movement, colors reset at the end
+    /// of every line, and trailing blanks trimmed.
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.buffer.chunks(self.width.max(1) as usize) {
+            let end = row
+                .iter()
+                .rposition(|cell| cell.symbol() != " " || cell.bg_color != Color::Reset)
+                .map_or(0, |last| last + 1);
+
+            let mut fg_color = Color::Reset;
+            let mut bg_color = Color::Reset;
+            for cell in &row[..end] {
+                if cell.symbol.is_continuation() {
+                    continue;
+                }
+                // A blank shows only its background, whatever its foreground
+                if cell.color != fg_color && cell.symbol() != " " {
+                    let _ = write!(text, "{}", SetForegroundColor(cell.color));
+                    fg_color = cell.color;
+                }
+                if cell.bg_color != bg_color {
+                    let _ = write!(text, "{}", SetBackgroundColor(cell.bg_color));
+                    bg_color = cell.bg_color;
+                }
+                text.push_str(cell.symbol());
+            }
+            if fg_color != Color::Reset || bg_color != Color::Reset {
+                let _ = write!(text, "{}", ResetColor);
             }
+            text.push('\n');
         }
-        Ok(())
+        text
+    }
+
+    /// Escape sequences flushed since the last call. Only a headless renderer
+    /// keeps them; on a terminal they have already been written out.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        self.target.take_output()
     }
 
     /// Renders the cursor at the specified position
     pub fn render_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
-        execute!(self.stdout, cursor::MoveTo(x, y), cursor::Show)?;
+        execute!(self.target, cursor::MoveTo(x, y), cursor::Show)?;
         Ok(())
     }
 
@@ -253,33 +464,36 @@ impl TerminalRenderer {
                 let cell = self.buffer[idx];
                 let last_cell = self.last_buffer[idx];
 
-                if cell != last_cell {
+                // The right half of a wide symbol is drawn along with its left half
+                if cell != last_cell && !cell.symbol.is_continuation() {
                     let expected_pos = last_pos.map(|(lx, ly)| (lx + 1, ly));
                     if expected_pos != Some((x, y)) {
-                        queue!(self.stdout, cursor::MoveTo(x, y))?;
+                        queue!(self.target, cursor::MoveTo(x, y))?;
                     }
 
                     if cell.color != current_fg_color {
-                        queue!(self.stdout, SetForegroundColor(cell.color))?;
+                        queue!(self.target, SetForegroundColor(cell.color))?;
                         current_fg_color = cell.color;
                     }
 
                     if cell.bg_color != current_bg_color {
-                        queue!(self.stdout, SetBackgroundColor(cell.bg_color))?;
+                        queue!(self.target, SetBackgroundColor(cell.bg_color))?;
                         current_bg_color = cell.bg_color;
                     }
 
-                    queue!(self.stdout, Print(cell.character))?;
-                    last_pos = Some((x, y));
+                    queue!(self.target, Print(cell.symbol.as_str()))?;
+                    let is_wide =
+                        x + 1 < self.width && self.buffer[idx + 1].symbol.is_continuation();
+                    last_pos = Some((if is_wide { x + 1 } else { x }, y));
                 }
             }
         }
 
         if current_fg_color != Color::Reset || current_bg_color != Color::Reset {
-            queue!(self.stdout, ResetColor)?;
+            queue!(self.target, ResetColor)?;
         }
 
-        self.stdout.flush()?;
+        self.target.flush()?;
         self.last_buffer.copy_from_slice(&self.buffer);
         Ok(())
     }
@@ -0,0 +1,182 @@
+use crossterm::style::Color;
+use std::sync::LazyLock;
+
+/// Bits kept per channel when indexing the lookup tables; 5 bits keeps them at
+/// 32 KiB each while staying within a few shades of the exact nearest color.
+const LOOKUP_BITS: u32 = 5;
+const LOOKUP_LEVELS: usize = 1 << LOOKUP_BITS;
+
+/// Channel levels of the xterm 6x6x6 color cube (indices 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The 16 ANSI colors as xterm draws them by default, in index order.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+static ANSI256_LOOKUP: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    // The first 16 entries are left out: terminals theme them freely
+    build_lookup(
+        &(16..=255)
+            .map(|i| (i, ansi_value_rgb(i)))
+            .collect::<Vec<_>>(),
+    )
+});
+
+static ANSI16_LOOKUP: LazyLock<Vec<u8>> = LazyLock::new(|| build_ansi_lookup(16));
+
+/// Only the first 8 colors work as backgrounds on every basic terminal.
+static ANSI8_LOOKUP: LazyLock<Vec<u8>> = LazyLock::new(|| build_ansi_lookup(8));
+
+/// Nearest xterm 256-color palette index for an RGB color.
+pub fn to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    ANSI256_LOOKUP[lookup_index(r, g, b)]
+}
+
+/// Nearest of the 16 basic ANSI colors for an RGB color.
+pub fn to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16[ANSI16_LOOKUP[lookup_index(r, g, b)] as usize].0
+}
+
+/// Nearest of the 8 non-bright ANSI colors, for backgrounds on basic terminals.
+pub fn to_ansi8(r: u8, g: u8, b: u8) -> Color {
+    ANSI16[ANSI8_LOOKUP[lookup_index(r, g, b)] as usize].0
+}
+
+/// RGB value xterm uses for a 256-color palette index.
+pub fn ansi_value_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI16[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+/// RGB value of a color as xterm shows it, or `None` for the terminal default.
+pub fn color_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(index) => Some(ansi_value_rgb(index)),
+        named => ANSI16
+            .iter()
+            .find(|(ansi, _)| *ansi == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn lookup_index(r: u8, g: u8, b: u8) -> usize {
+    let shift = 8 - LOOKUP_BITS;
+    ((r >> shift) as usize * LOOKUP_LEVELS + (g >> shift) as usize) * LOOKUP_LEVELS
+        + (b >> shift) as usize
+}
+
+fn build_ansi_lookup(count: usize) -> Vec<u8> {
+    build_lookup(
+        &ANSI16[..count]
+            .iter()
+            .enumerate()
+            .map(|(i, (_, rgb))| (i as u8, *rgb))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// For every quantized RGB cell, the palette entry nearest to the cell's center.
+fn build_lookup(palette: &[(u8, (u8, u8, u8))]) -> Vec<u8> {
+    let step = 256 / LOOKUP_LEVELS;
+    let center = |level: usize| (level * step + step / 2) as u8;
+    let mut table = Vec::with_capacity(LOOKUP_LEVELS.pow(3));
+
+    for r in 0..LOOKUP_LEVELS {
+        for g in 0..LOOKUP_LEVELS {
+            for b in 0..LOOKUP_LEVELS {
+                let rgb = (center(r), center(g), center(b));
+                let nearest = palette
+                    .iter()
+                    .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+                    .map_or(0, |(index, _)| *index);
+                table.push(nearest);
+            }
+        }
+    }
+    table
+}
+
+/// Squared distance weighted toward green, to which the eye is most sensitive.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    2 * d(a.0, b.0) + 4 * d(a.1, b.1) + 3 * d(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi256_exact_and_nearest() {
+        assert_eq!(to_ansi256(255, 0, 0), 196);
+        assert_eq!(to_ansi256(0, 0, 0), 16);
+        assert_eq!(to_ansi256(255, 255, 255), 231);
+        // Greys stay neutral instead of picking up a tint from the cube
+        let (r, g, b) = ansi_value_rgb(to_ansi256(128, 128, 128));
+        assert!(r == g && g == b && r.abs_diff(128) <= 8);
+        // Warm tones keep their hue
+        assert_eq!(to_ansi256(210, 180, 140), 180);
+        assert_eq!(to_ansi256(139, 69, 19), 94);
+    }
+
+    #[test]
+    fn test_ansi16_nearest() {
+        assert_eq!(to_ansi16(250, 10, 10), Color::Red);
+        assert_eq!(to_ansi16(0, 100, 0), Color::DarkGreen);
+        assert_eq!(to_ansi16(20, 20, 30), Color::Black);
+        assert_eq!(to_ansi16(210, 180, 140), Color::Grey);
+        assert_eq!(to_ansi16(255, 170, 60), Color::Yellow);
+        // Browns land on the dark yellow most terminals draw as brown
+        assert_eq!(to_ansi16(101, 67, 33), Color::DarkYellow);
+        // Bright colors are out of reach as backgrounds
+        assert_eq!(to_ansi8(140, 190, 240), Color::Grey);
+        assert_eq!(to_ansi8(250, 10, 10), Color::DarkRed);
+    }
+
+    #[test]
+    fn test_ansi_value_rgb() {
+        assert_eq!(ansi_value_rgb(9), (255, 0, 0));
+        assert_eq!(ansi_value_rgb(21), (0, 0, 255));
+        assert_eq!(ansi_value_rgb(232), (8, 8, 8));
+        assert_eq!(ansi_value_rgb(255), (238, 238, 238));
+    }
+
+    #[test]
+    fn test_color_rgb() {
+        assert_eq!(color_rgb(Color::Reset), None);
+        assert_eq!(color_rgb(Color::DarkGreen), Some((0, 128, 0)));
+        assert_eq!(color_rgb(Color::AnsiValue(21)), Some((0, 0, 255)));
+        assert_eq!(color_rgb(Color::Rgb { r: 1, g: 2, b: 3 }), Some((1, 2, 3)));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::error::TerminalError;
+use crossterm::{
+    cursor, execute,
+    style::ResetColor,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::{self, BufWriter, Stdout, Write};
+
+/// Where a renderer sends its frames. The renderer writes escape sequences to
+/// it; the target decides how the output device is set up and torn down.
+pub trait RenderTarget: Write + Send {
+    /// Prepares the device for full-screen drawing.
+    fn enter(&mut self) -> Result<(), TerminalError>;
+
+    /// Gives the device back the way `enter` found it.
+    fn leave(&mut self) -> io::Result<()>;
+
+    /// Output written since the last call, for targets that keep it.
+    fn take_output(&mut self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+/// The real terminal, drawn on in raw mode on the alternate screen.
+pub struct TerminalTarget {
+    stdout: BufWriter<Stdout>,
+}
+
+impl TerminalTarget {
+    pub fn new() -> Self {
+        Self {
+            stdout: BufWriter::new(io::stdout()),
+        }
+    }
+}
+
+impl Default for TerminalTarget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for TerminalTarget {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stdout.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdout.flush()
+    }
+}
+
+impl RenderTarget for TerminalTarget {
+    fn enter(&mut self) -> Result<(), TerminalError> {
+        terminal::enable_raw_mode().map_err(TerminalError::RawModeError)?;
+        execute!(self.stdout, EnterAlternateScreen, cursor::Hide)
+            .map_err(TerminalError::InitError)?;
+        Ok(())
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        execute!(self.stdout, LeaveAlternateScreen, cursor::Show, ResetColor)?;
+        terminal::disable_raw_mode()?;
+        Ok(())
+    }
+}
+
+/// A target with no device behind it, for rendering without a terminal. The
+/// escape sequences are held until taken, so they can be saved instead.
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessTarget {
+    output: Vec<u8>,
+}
+
+impl Write for HeadlessTarget {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl RenderTarget for HeadlessTarget {
+    fn enter(&mut self) -> Result<(), TerminalError> {
+        Ok(())
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+}
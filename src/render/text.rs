@@ -0,0 +1,106 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Bytes of a grapheme cluster a cell can hold. Longer clusters, like long
+/// emoji ZWJ sequences, are cut back to their first character.
+const MAX_SYMBOL_BYTES: usize = 15;
+
+/// Columns a character takes up in the terminal: 0, 1 or 2. Controls take none.
+pub fn char_width(ch: char) -> usize {
+    UnicodeWidthChar::width(ch).unwrap_or(0)
+}
+
+/// Columns a grapheme cluster takes up, counting emoji presentation, flag and
+/// ZWJ sequences as one wide character.
+pub fn grapheme_width(grapheme: &str) -> usize {
+    UnicodeWidthStr::width(grapheme).min(2)
+}
+
+/// Columns `text` takes up on screen.
+pub fn display_width(text: &str) -> usize {
+    text.graphemes(true).map(grapheme_width).sum()
+}
+
+/// What a cell shows: one grapheme cluster stored inline, so cells stay
+/// `Copy`. The empty symbol marks the right half of a double-width one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    bytes: [u8; MAX_SYMBOL_BYTES],
+    len: u8,
+}
+
+impl Symbol {
+    pub const CONTINUATION: Self = Self {
+        bytes: [0; MAX_SYMBOL_BYTES],
+        len: 0,
+    };
+
+    pub fn from_char(ch: char) -> Self {
+        let mut symbol = Self::CONTINUATION;
+        symbol.len = ch.encode_utf8(&mut symbol.bytes).len() as u8;
+        symbol
+    }
+
+    pub fn from_grapheme(grapheme: &str) -> Self {
+        if grapheme.len() > MAX_SYMBOL_BYTES {
+            return grapheme
+                .chars()
+                .next()
+                .map_or(Self::CONTINUATION, Self::from_char);
+        }
+        let mut symbol = Self::CONTINUATION;
+        symbol.bytes[..grapheme.len()].copy_from_slice(grapheme.as_bytes());
+        symbol.len = grapheme.len() as u8;
+        symbol
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+
+    pub fn first_char(&self) -> Option<char> {
+        self.as_str().chars().next()
+    }
+
+    pub fn is_continuation(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_width() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('°'), 1);
+        assert_eq!(char_width('東'), 2);
+        assert_eq!(char_width('\u{0301}'), 0);
+        assert_eq!(char_width('\u{0941}'), 0);
+        assert_eq!(char_width('\u{0007}'), 0);
+        assert_eq!(char_width('⛅'), 2);
+        assert_eq!(char_width('☀'), 1);
+    }
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("12°C"), 4);
+        assert_eq!(display_width("東京"), 4);
+        assert_eq!(display_width("Zu\u{0308}rich"), 6);
+        assert_eq!(display_width("☀\u{FE0F} 20°"), 6);
+        assert_eq!(display_width("👨\u{200D}👩\u{200D}👧"), 2);
+        assert_eq!(display_width("🇯🇵"), 2);
+        assert_eq!(display_width("नमस्ते"), 4);
+    }
+
+    #[test]
+    fn test_symbol_round_trip() {
+        assert_eq!(Symbol::from_char('東').as_str(), "東");
+        assert_eq!(Symbol::from_grapheme("u\u{0308}").as_str(), "u\u{0308}");
+        assert!(Symbol::CONTINUATION.is_continuation());
+
+        let family = "👨\u{200D}👩\u{200D}👧\u{200D}👦";
+        assert_eq!(Symbol::from_grapheme(family).as_str(), "👨");
+    }
+}
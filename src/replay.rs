@@ -0,0 +1,398 @@
+use crate::error::ReplayError;
+use crate::history::HistoryRecord;
+use crate::simulate::{SimulateParams, TimeOfDay};
+use crate::weather::{WeatherCondition, WeatherData};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Slowest and fastest playback, from a hundredth of real time up to a day a second.
+pub const MIN_SPEED: f64 = 0.01;
+pub const MAX_SPEED: f64 = 86_400.0;
+
+/// A weather snapshot and when it takes effect, relative to the start of the replay.
+#[derive(Debug, Clone)]
+pub struct ReplayFrame {
+    pub offset: Duration,
+    pub data: WeatherData,
+}
+
+/// A timed sequence of weather snapshots, loaded from a recorded history log or a
+/// hand-written TOML/JSON script, played back at `speed` times real time.
+#[derive(Debug, Clone)]
+pub struct ReplayTimeline {
+    frames: Vec<ReplayFrame>,
+    speed: f64,
+    source: String,
+}
+
+#[derive(Deserialize)]
+struct ReplayScript {
+    #[serde(alias = "frame")]
+    frames: Vec<ScriptFrame>,
+}
+
+/// Time of a scripted frame: seconds from the start, or a "HH:MM[:SS]" clock offset.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScriptOffset {
+    Seconds(f64),
+    Clock(String),
+}
+
+/// A hand-written frame. Only `at` and `condition` are required; everything else
+/// falls back to the same values `--simulate` uses.
+#[derive(Deserialize)]
+struct ScriptFrame {
+    at: ScriptOffset,
+    condition: WeatherCondition,
+    temperature: Option<f64>,
+    apparent_temperature: Option<f64>,
+    humidity: Option<f64>,
+    precipitation: Option<f64>,
+    wind_speed: Option<f64>,
+    wind_direction: Option<f64>,
+    cloud_cover: Option<f64>,
+    pressure: Option<f64>,
+    visibility: Option<f64>,
+    #[serde(default = "default_is_day")]
+    is_day: bool,
+    moon_phase: Option<f64>,
+}
+
+fn default_is_day() -> bool {
+    true
+}
+
+impl ScriptOffset {
+    fn to_duration(&self) -> Result<Duration, ReplayError> {
+        let invalid = || ReplayError::InvalidOffset(self.describe());
+        match self {
+            Self::Seconds(secs) => Duration::try_from_secs_f64(*secs).map_err(|_| invalid()),
+            Self::Clock(clock) => {
+                let parts = clock
+                    .split(':')
+                    .map(|part| part.trim().parse::<u64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| invalid())?;
+                let secs = match parts.as_slice() {
+                    [h, m] if *m < 60 => h * 3600 + m * 60,
+                    [h, m, s] if *m < 60 && *s < 60 => h * 3600 + m * 60 + s,
+                    _ => return Err(invalid()),
+                };
+                Ok(Duration::from_secs(secs))
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Seconds(secs) => secs.to_string(),
+            Self::Clock(clock) => format!("\"{}\"", clock),
+        }
+    }
+}
+
+impl ScriptFrame {
+    fn into_frame(self) -> Result<ReplayFrame, ReplayError> {
+        let offset = self.at.to_duration()?;
+        let params = SimulateParams {
+            temperature: self.temperature,
+            wind_speed: self.wind_speed,
+            wind_direction: self.wind_direction,
+            precipitation: self.precipitation,
+            cloud_cover: self.cloud_cover,
+            visibility: self.visibility,
+            moon_phase: self.moon_phase,
+            time_of_day: Some(if self.is_day {
+                TimeOfDay::Day
+            } else {
+                TimeOfDay::Night
+            }),
+        };
+
+        // Scripts can also set the readings `--simulate` keeps fixed
+        let mut data = params.weather(self.condition);
+        data.apparent_temperature = self
+            .apparent_temperature
+            .unwrap_or(data.apparent_temperature);
+        data.humidity = self.humidity.unwrap_or(data.humidity);
+        data.pressure = self.pressure.unwrap_or(data.pressure);
+        data.timestamp = "replay".to_string();
+
+        Ok(ReplayFrame { offset, data })
+    }
+}
+
+impl ReplayTimeline {
+    /// Loads a timeline, choosing the format by extension: `.jsonl` is a history log
+    /// written by `--history`, `.toml` and `.json` are scripts with a `frames` list.
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        let display = path.display().to_string();
+        let contents = fs::read_to_string(path).map_err(|source| ReplayError::ReadError {
+            path: display.clone(),
+            source,
+        })?;
+        let parse_error = |message: String| ReplayError::ParseError {
+            path: display.clone(),
+            message,
+        };
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        let timeline = match extension.as_deref() {
+            Some("jsonl") => Self::from_history(&contents).map_err(parse_error)?,
+            Some("toml") => {
+                let script: ReplayScript =
+                    toml::from_str(&contents).map_err(|e| parse_error(e.to_string()))?;
+                Self::from_script(script)?
+            }
+            Some("json") => {
+                let script: ReplayScript =
+                    serde_json::from_str(&contents).map_err(|e| parse_error(e.to_string()))?;
+                Self::from_script(script)?
+            }
+            _ => return Err(ReplayError::UnsupportedFormat(display)),
+        };
+
+        if timeline.frames.is_empty() {
+            return Err(ReplayError::Empty(display));
+        }
+        let source = path
+            .file_name()
+            .map_or(display, |name| name.to_string_lossy().into_owned());
+        Ok(Self { source, ..timeline })
+    }
+
+    fn from_history(contents: &str) -> Result<Self, String> {
+        let mut records = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str::<HistoryRecord>(line)
+                    .map_err(|e| format!("line {}: {}", i + 1, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        records.sort_by_key(|record| record.recorded_at);
+
+        let start = records.first().map_or(0, |record| record.recorded_at);
+        let frames = records
+            .into_iter()
+            .map(|record| ReplayFrame {
+                offset: Duration::from_secs(record.recorded_at - start),
+                data: record.data,
+            })
+            .collect();
+        Ok(Self::new(frames))
+    }
+
+    fn from_script(script: ReplayScript) -> Result<Self, ReplayError> {
+        let frames = script
+            .frames
+            .into_iter()
+            .map(ScriptFrame::into_frame)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(frames))
+    }
+
+    pub fn new(mut frames: Vec<ReplayFrame>) -> Self {
+        frames.sort_by_key(|frame| frame.offset);
+        Self {
+            frames,
+            speed: 1.0,
+            source: String::from("replay"),
+        }
+    }
+
+    /// Plays back `speed` times faster than recorded (e.g. 1440 plays a day in a minute),
+    /// kept within `MIN_SPEED..=MAX_SPEED`.
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = if speed.is_nan() {
+            1.0
+        } else {
+            speed.clamp(MIN_SPEED, MAX_SPEED)
+        };
+        self
+    }
+
+    /// File name the timeline was loaded from, shown in place of the provider name.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn frames(&self) -> &[ReplayFrame] {
+        &self.frames
+    }
+
+    /// How long to wait before each frame, scaled by the playback speed.
+    pub fn schedule(&self) -> Vec<(Duration, WeatherData)> {
+        let mut previous = Duration::ZERO;
+        self.frames
+            .iter()
+            .map(|frame| {
+                let wait = (frame.offset - previous).div_f64(self.speed);
+                previous = frame.offset;
+                (wait, frame.data.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("weathr_replay_{}_{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_toml_script_with_defaults() {
+        let path = write_temp(
+            "day.toml",
+            r#"
+[[frames]]
+at = "00:00"
+condition = "clear"
+
+[[frames]]
+at = "06:30"
+condition = "thunderstorm"
+temperature = 14.0
+
+[[frames]]
+at = 3600
+condition = "rain"
+"#,
+        );
+        let timeline = ReplayTimeline::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let frames = timeline.frames();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].offset, Duration::from_secs(3600));
+        assert_eq!(frames[1].data.condition, WeatherCondition::Rain);
+        assert_eq!(frames[1].data.precipitation, 2.5);
+        assert_eq!(frames[2].offset, Duration::from_secs(6 * 3600 + 30 * 60));
+        assert_eq!(frames[2].data.temperature, 14.0);
+        assert_eq!(frames[2].data.apparent_temperature, 13.0);
+        // Unset readings match what `--simulate` would show
+        let simulated = SimulateParams {
+            temperature: Some(14.0),
+            ..SimulateParams::default()
+        }
+        .weather(WeatherCondition::Thunderstorm);
+        assert_eq!(frames[2].data.humidity, simulated.humidity);
+        assert_eq!(frames[2].data.pressure, simulated.pressure);
+        assert_eq!(frames[2].data.cloud_cover, simulated.cloud_cover);
+        assert_eq!(frames[2].data.wind_speed, 45.0);
+    }
+
+    #[test]
+    fn test_load_json_script() {
+        let path = write_temp(
+            "night.json",
+            r#"{"frames": [{"at": 0, "condition": "snow", "is_day": false}]}"#,
+        );
+        let timeline = ReplayTimeline::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert!(!timeline.frames()[0].data.is_day);
+        assert!(timeline.source().ends_with("night.json"));
+    }
+
+    #[test]
+    fn test_load_history_log_is_relative_to_first_record() {
+        let record = |recorded_at: u64| {
+            let frame = ScriptFrame {
+                at: ScriptOffset::Seconds(0.0),
+                condition: WeatherCondition::Cloudy,
+                temperature: None,
+                apparent_temperature: None,
+                humidity: None,
+                precipitation: None,
+                wind_speed: None,
+                wind_direction: None,
+                cloud_cover: None,
+                pressure: None,
+                visibility: None,
+                is_day: true,
+                moon_phase: None,
+            };
+            let data = frame.into_frame().unwrap().data;
+            serde_json::to_string(&HistoryRecord { recorded_at, data }).unwrap()
+        };
+        let path = write_temp(
+            "history.jsonl",
+            &format!("{}\n{}\n", record(10_900), record(10_000)),
+        );
+        let timeline = ReplayTimeline::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let offsets: Vec<_> = timeline.frames().iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, vec![Duration::ZERO, Duration::from_secs(900)]);
+    }
+
+    #[test]
+    fn test_schedule_applies_speed() {
+        let path = write_temp(
+            "speed.toml",
+            r#"
+[[frames]]
+at = 0
+condition = "clear"
+
+[[frames]]
+at = "01:00"
+condition = "rain"
+"#,
+        );
+        let timeline = ReplayTimeline::load(&path).unwrap().with_speed(60.0);
+        let _ = fs::remove_file(&path);
+
+        let waits: Vec<_> = timeline
+            .schedule()
+            .into_iter()
+            .map(|(wait, _)| wait)
+            .collect();
+        assert_eq!(waits, vec![Duration::ZERO, Duration::from_secs(60)]);
+
+        // Speeds too small to schedule are held at the slowest playback
+        let slow = ReplayTimeline::new(timeline.frames().to_vec()).with_speed(1e-20);
+        assert_eq!(slow.schedule()[1].0, Duration::from_secs(3600 * 100));
+    }
+
+    #[test]
+    fn test_load_rejects_bad_input() {
+        let bad_offset = write_temp(
+            "bad.toml",
+            "[[frames]]\nat = \"6:75\"\ncondition = \"clear\"\n",
+        );
+        assert!(matches!(
+            ReplayTimeline::load(&bad_offset),
+            Err(ReplayError::InvalidOffset(_))
+        ));
+        let _ = fs::remove_file(&bad_offset);
+
+        let empty = write_temp("empty.json", r#"{"frames": []}"#);
+        assert!(matches!(
+            ReplayTimeline::load(&empty),
+            Err(ReplayError::Empty(_))
+        ));
+        let _ = fs::remove_file(&empty);
+
+        let unknown = write_temp("weather.csv", "");
+        assert!(matches!(
+            ReplayTimeline::load(&unknown),
+            Err(ReplayError::UnsupportedFormat(_))
+        ));
+        let _ = fs::remove_file(&unknown);
+    }
+}
@@ -0,0 +1,180 @@
+use rand::RngExt;
+use serde::Deserialize;
+use std::future::Future;
+use std::time::Duration;
+
+/// Exponential backoff with jitter, shared by weather fetches and geolocation.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_delay_ms: u64,
+    pub max_delay_ms: u64,
+    /// Fraction of the delay to randomize by (0.25 means ±25%).
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 500,
+            max_delay_ms: 10_000,
+            jitter: 0.25,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn attempts(&self) -> u32 {
+        self.max_attempts.max(1)
+    }
+
+    /// Delay before the retry following the given (1-based) failed attempt, without jitter.
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay_ms = self
+            .initial_delay_ms
+            .saturating_mul(2_u64.pow(exponent))
+            .min(self.max_delay_ms);
+        Duration::from_millis(delay_ms)
+    }
+
+    pub fn delay(&self, attempt: u32, rng: &mut impl rand::Rng) -> Duration {
+        let base = self.base_delay(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 || base.is_zero() {
+            return base;
+        }
+
+        let factor = rng.random_range((1.0 - jitter)..=(1.0 + jitter));
+        base.mul_f64(factor)
+    }
+
+    /// Runs `operation` until it succeeds, fails with an error `should_retry` rejects,
+    /// or the attempt budget is spent. The last error is returned on failure.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        mut operation: F,
+        should_retry: impl Fn(&E) -> bool,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let attempts = self.attempts();
+        let mut attempt = 1;
+
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    if attempt >= attempts || !should_retry(&e) {
+                        return Err(e);
+                    }
+
+                    let delay = self.delay(attempt, &mut rand::rng());
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn instant_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            initial_delay_ms: 0,
+            max_delay_ms: 0,
+            jitter: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_base_delay_doubles_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_delay_ms: 500,
+            max_delay_ms: 3000,
+            jitter: 0.0,
+        };
+        assert_eq!(policy.base_delay(1), Duration::from_millis(500));
+        assert_eq!(policy.base_delay(2), Duration::from_millis(1000));
+        assert_eq!(policy.base_delay(3), Duration::from_millis(2000));
+        assert_eq!(policy.base_delay(4), Duration::from_millis(3000));
+        assert_eq!(policy.base_delay(40), Duration::from_millis(3000));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..RetryPolicy::default()
+        };
+        let mut rng = rand::rng();
+        for _ in 0..100 {
+            let delay = policy.delay(1, &mut rng);
+            assert!(delay >= Duration::from_millis(250));
+            assert!(delay <= Duration::from_millis(750));
+        }
+    }
+
+    #[test]
+    fn test_zero_attempts_still_tries_once() {
+        assert_eq!(instant_policy(0).attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let result: Result<u32, &str> = instant_policy(3)
+            .run(
+                || async {
+                    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    if n < 3 { Err("transient") } else { Ok(n) }
+                },
+                |_| true,
+            )
+            .await;
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_on_non_retryable_error() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), &str> = instant_policy(5)
+            .run(
+                || async {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Err("fatal")
+                },
+                |_| false,
+            )
+            .await;
+        assert_eq!(result, Err("fatal"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), &str> = instant_policy(4)
+            .run(
+                || async {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Err("transient")
+                },
+                |_| true,
+            )
+            .await;
+        assert_eq!(result, Err("transient"));
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+}
@@ -1,4 +1,7 @@
+use super::snow_cover::is_covered;
 use crate::render::TerminalRenderer;
+use crate::season::Season;
+use crate::theme::Palette;
 use crossterm::style::Color;
 use std::io;
 
@@ -11,6 +14,8 @@ pub struct DecorationRenderConfig {
     pub house_width: u16,
     pub width: u16,
     pub is_day: bool,
+    pub snow_depth: f32,
+    pub season: Season,
 }
 
 impl Decorations {
@@ -23,128 +28,194 @@ impl Decorations {
         renderer: &mut TerminalRenderer,
         config: &DecorationRenderConfig,
     ) -> io::Result<()> {
+        let palette = renderer.palette(config.is_day);
+
         // Render Tree (Left of house)
-        let (tree_lines, tree_color) = self.get_tree(config.is_day);
+        let (tree_lines, tree_color, accent) =
+            self.get_tree(config.is_day, config.season, palette.ground.foliage);
         let tree_height = tree_lines.len() as u16;
         let tree_y = config.horizon_y.saturating_sub(tree_height);
         let tree_x = config.house_x.saturating_sub(20);
 
         if tree_x > 0 {
-            for (i, line) in tree_lines.iter().enumerate() {
-                for (j, ch) in line.chars().enumerate() {
-                    if ch != ' ' {
-                        renderer.render_char(
-                            tree_x + j as u16,
-                            tree_y + i as u16,
-                            ch,
-                            tree_color,
-                        )?;
-                    }
-                }
-            }
+            let color_of = |ch| match accent {
+                Some((accent_ch, accent_color)) if ch == accent_ch => accent_color,
+                _ => tree_color,
+            };
+            self.render_sprite(renderer, &tree_lines, tree_x, tree_y, color_of, config)?;
         }
 
         // Render Fence (Right of house)
-        let (fence_lines, fence_color) = self.get_fence(config.is_day);
+        let (fence_lines, fence_color) = self.get_fence(&palette);
         let fence_height = fence_lines.len() as u16;
         let fence_y = config.horizon_y.saturating_sub(fence_height); // Sitting on ground
         let fence_x = config.house_x + config.house_width + 2; // Slight gap
 
         if fence_x < config.width {
-            for (i, line) in fence_lines.iter().enumerate() {
-                for (j, ch) in line.chars().enumerate() {
-                    if ch != ' ' {
-                        renderer.render_char(
-                            fence_x + j as u16,
-                            fence_y + i as u16,
-                            ch,
-                            fence_color,
-                        )?;
-                    }
-                }
-            }
+            self.render_sprite(
+                renderer,
+                &fence_lines,
+                fence_x,
+                fence_y,
+                |_| fence_color,
+                config,
+            )?;
         }
 
         // Render Mailbox (On ground top level, left of tree)
-        let (mailbox_lines, mailbox_color) = self.get_mailbox(config.is_day);
+        let (mailbox_lines, mailbox_color) = self.get_mailbox(&palette);
         let mailbox_height = mailbox_lines.len() as u16;
         let mailbox_x = tree_x.saturating_sub(10); // Left of tree
         let mailbox_y = config.horizon_y.saturating_sub(mailbox_height); // On ground top
 
         if mailbox_x < config.width {
-            for (i, line) in mailbox_lines.iter().enumerate() {
-                for (j, ch) in line.chars().enumerate() {
-                    if ch != ' ' {
-                        renderer.render_char(
-                            mailbox_x + j as u16,
-                            mailbox_y + i as u16,
-                            ch,
-                            mailbox_color,
-                        )?;
-                    }
-                }
-            }
+            self.render_sprite(
+                renderer,
+                &mailbox_lines,
+                mailbox_x,
+                mailbox_y,
+                |_| mailbox_color,
+                config,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Pine trees standing on the horizon at each of the `xs` columns.
+    pub fn render_pines(
+        &self,
+        renderer: &mut TerminalRenderer,
+        xs: &[u16],
+        config: &DecorationRenderConfig,
+    ) -> io::Result<()> {
+        let palette = renderer.palette(config.is_day);
+        let (pine_lines, pine_color) = self.get_pine_tree(&palette);
+        let pine_y = config.horizon_y.saturating_sub(pine_lines.len() as u16);
+
+        for &pine_x in xs {
+            self.render_sprite(
+                renderer,
+                &pine_lines,
+                pine_x,
+                pine_y,
+                |_| pine_color,
+                config,
+            )?;
         }
+        Ok(())
+    }
 
-        // Render Second Tree (Right of house, only if terminal is wide enough)
-        if config.width > 120 {
-            let (pine_lines, pine_color) = self.get_pine_tree(config.is_day);
-            let pine_height = pine_lines.len() as u16;
-            let pine_x = config.house_x + config.house_width + 18; // 18 chars right of house
-            let pine_y = config.horizon_y.saturating_sub(pine_height);
-
-            if pine_x + 10 < config.width {
-                // Check if full tree fits
-                for (i, line) in pine_lines.iter().enumerate() {
-                    for (j, ch) in line.chars().enumerate() {
-                        if ch != ' ' {
-                            renderer.render_char(
-                                pine_x + j as u16,
-                                pine_y + i as u16,
-                                ch,
-                                pine_color,
-                            )?;
-                        }
-                    }
+    /// Draws a sprite, with snow settling on the topmost cell of each column.
+    fn render_sprite(
+        &self,
+        renderer: &mut TerminalRenderer,
+        lines: &[&str],
+        x: u16,
+        y: u16,
+        color_of: impl Fn(char) -> Color,
+        config: &DecorationRenderConfig,
+    ) -> io::Result<()> {
+        let snow_color = renderer.palette(config.is_day).ground.snow;
+        let mut column_has_top = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            for (j, ch) in line.chars().enumerate() {
+                if ch == ' ' {
+                    continue;
                 }
+                if column_has_top.len() <= j {
+                    column_has_top.resize(j + 1, false);
+                }
+                let is_top = !column_has_top[j];
+                column_has_top[j] = true;
+
+                let (col, row) = (x + j as u16, y + i as u16);
+                let snowy =
+                    is_top && is_covered(col as usize, row as usize, config.snow_depth * 1.5);
+                let color = if snowy { snow_color } else { color_of(ch) };
+                renderer.render_char(col, row, ch, color)?;
             }
         }
-
         Ok(())
     }
 
-    fn get_tree(&self, is_day: bool) -> (Vec<&'static str>, Color) {
-        (
-            vec![
-                "      ####      ",
-                "    ########    ",
-                "   ##########   ",
-                "    ########    ",
-                "      _||_      ",
-            ],
+    /// The deciduous tree through the year, with blossoms or turning leaves drawn
+    /// in an accent color. `foliage` is its summer green.
+    fn get_tree(
+        &self,
+        is_day: bool,
+        season: Season,
+        foliage: Color,
+    ) -> (Vec<&'static str>, Color, Option<(char, Color)>) {
+        let shade = |r: u8, g: u8, b: u8| {
             if is_day {
-                Color::DarkGreen
+                Color::Rgb { r, g, b }
             } else {
-                Color::Rgb { r: 0, g: 50, b: 0 }
-            },
-        )
+                Color::Rgb {
+                    r: r / 2,
+                    g: g / 2,
+                    b: b / 2,
+                }
+            }
+        };
+        match season {
+            Season::Spring => (
+                vec![
+                    "      #@##      ",
+                    "    ##@###@#    ",
+                    "   #@####@###   ",
+                    "    ###@##@#    ",
+                    "      _||_      ",
+                ],
+                foliage,
+                Some(('@', shade(255, 170, 200))),
+            ),
+            Season::Summer => (
+                vec![
+                    "      ####      ",
+                    "    ########    ",
+                    "   ##########   ",
+                    "    ########    ",
+                    "      _||_      ",
+                ],
+                foliage,
+                None,
+            ),
+            Season::Autumn => (
+                vec![
+                    "      #%##      ",
+                    "    #%##%#%#    ",
+                    "   %##%###%#%   ",
+                    "    ##%#%##%    ",
+                    "      _||_      ",
+                ],
+                shade(210, 105, 30),
+                Some(('%', shade(218, 165, 32))),
+            ),
+            Season::Winter => (
+                vec![
+                    "    \\  \\/  /    ",
+                    "  \\_ \\ || / _/  ",
+                    "     \\_||_/     ",
+                    "       ||       ",
+                    "      _||_      ",
+                ],
+                shade(120, 85, 55),
+                None,
+            ),
+        }
     }
 
-    fn get_fence(&self, is_day: bool) -> (Vec<&'static str>, Color) {
-        (
-            vec!["|--|--|--|--|", "|  |  |  |  |"],
-            if is_day { Color::White } else { Color::Grey },
-        )
+    fn get_fence(&self, palette: &Palette) -> (Vec<&'static str>, Color) {
+        (vec!["|--|--|--|--|", "|  |  |  |  |"], palette.house.fence)
     }
 
-    fn get_mailbox(&self, is_day: bool) -> (Vec<&'static str>, Color) {
-        (
-            vec![" ___ ", "|___|", "  |  "],
-            if is_day { Color::Blue } else { Color::DarkBlue },
-        )
+    fn get_mailbox(&self, palette: &Palette) -> (Vec<&'static str>, Color) {
+        (vec![" ___ ", "|___|", "  |  "], palette.house.mailbox)
     }
 
-    fn get_pine_tree(&self, is_day: bool) -> (Vec<&'static str>, Color) {
+    fn get_pine_tree(&self, palette: &Palette) -> (Vec<&'static str>, Color) {
         (
             vec![
                 "    *    ",
@@ -153,11 +224,7 @@ impl Decorations {
                 " ******* ",
                 "   |||   ",
             ],
-            if is_day {
-                Color::DarkGreen
-            } else {
-                Color::Rgb { r: 0, g: 50, b: 0 }
-            },
+            palette.ground.foliage,
         )
     }
 }
@@ -1,26 +1,54 @@
+use super::snow_cover::is_covered;
+use super::wetness::is_puddle;
 use crate::render::TerminalRenderer;
+use crate::season::Season;
 use crossterm::style::Color;
 use std::io;
 
 #[derive(Default)]
 pub struct Ground;
 
+pub struct GroundRenderConfig {
+    pub width: u16,
+    pub height: u16,
+    pub y_start: u16,
+    pub is_day: bool,
+    pub snow_depth: f32,
+    pub wetness: f32,
+    /// Share of the grass tinted by frost, from 0.0 to 1.0.
+    pub frost: f32,
+    pub season: Season,
+}
+
 impl Ground {
     pub fn render(
         &self,
         renderer: &mut TerminalRenderer,
-        width: u16,
-        height: u16,
-        y_start: u16,
-        is_day: bool,
+        config: &GroundRenderConfig,
     ) -> io::Result<()> {
-        let width = width as usize;
-        let height = height as usize;
+        let width = config.width as usize;
+        let height = config.height as usize;
+        let (y_start, is_day) = (config.y_start, config.is_day);
+        let (snow_depth, wetness) = (config.snow_depth, config.wetness);
+        let colors = renderer.palette(is_day).ground;
 
-        let grass_colors = if is_day {
-            [Color::Green, Color::DarkGreen]
-        } else {
-            [Color::DarkGreen, Color::Rgb { r: 0, g: 50, b: 0 }]
+        let dim = |r: u8, g: u8, b: u8| {
+            if is_day {
+                Color::Rgb { r, g, b }
+            } else {
+                Color::Rgb {
+                    r: r / 2,
+                    g: g / 2,
+                    b: b / 2,
+                }
+            }
+        };
+        // Lush and flowering in spring, straw-colored in autumn, dull in winter
+        let (grass_colors, flower_percent) = match config.season {
+            Season::Spring => ([dim(90, 200, 70), dim(40, 140, 40)], 9),
+            Season::Summer => ([colors.grass, colors.grass_blade], 5),
+            Season::Autumn => ([dim(170, 150, 60), dim(150, 95, 40)], 1),
+            Season::Winter => ([dim(110, 120, 95), dim(85, 95, 75)], 0),
         };
 
         let flower_colors = if is_day {
@@ -34,18 +62,18 @@ impl Ground {
             ]
         };
 
-        let soil_color = if is_day {
-            Color::Rgb {
-                r: 101,
-                g: 67,
-                b: 33,
-            }
-        } else {
-            Color::Rgb {
-                r: 60,
-                g: 40,
-                b: 20,
+        // Wet soil darkens toward roughly half its dry brightness
+        let soil_shade = 1.0 - 0.45 * wetness;
+        let soil_color = match colors.soil {
+            Color::Rgb { r, g, b } => {
+                let shade = |value: u8| (value as f32 * soil_shade) as u8;
+                Color::Rgb {
+                    r: shade(r),
+                    g: shade(g),
+                    b: shade(b),
+                }
             }
+            named => named,
         };
 
         // Simple seeded RNG for deterministic decoration positions
@@ -55,13 +83,22 @@ impl Ground {
 
         for y in 0..height {
             for x in 0..width {
-                let (ch, color) = if y == 0 {
+                // Snow covers the grass first and drifts into the soil row below once deep
+                let (ch, color) = if y == 0 && is_covered(x, y, snow_depth * 2.0) {
+                    (if snow_depth > 0.5 { '~' } else { '.' }, colors.snow)
+                } else if y == 1 && is_covered(x, y, snow_depth * 2.0 - 1.0) {
+                    ('.', colors.snow)
+                } else if is_puddle(x, y, wetness) {
+                    ('~', colors.puddle)
+                } else if y == 0 {
                     // Top layer: Grass/Flowers only (no path)
                     let r = pseudo_rand(x, y);
-                    if r < 5 {
-                        // 5% chance of flower
+                    if r < flower_percent {
                         let f_idx = (x + y) % flower_colors.len();
                         ('*', flower_colors[f_idx])
+                    } else if is_covered(x, y + height, config.frost) {
+                        // Frost whitens the blades, using other cells than the snow
+                        (if r < 15 { ',' } else { '^' }, colors.frost)
                     } else if r < 15 {
                         // 10% chance of distinct grass blade
                         (',', grass_colors[1])
@@ -0,0 +1,71 @@
+use super::layout::SceneLayout;
+use super::snow_cover::is_covered;
+use crate::render::TerminalRenderer;
+use std::io;
+
+/// Rows the hills rise above the lawn at most.
+const MAX_HEIGHT: f32 = 3.0;
+
+/// A line of rolling hills along the horizon, behind the houses and trees.
+#[derive(Default)]
+pub struct Hills;
+
+impl Hills {
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        layout: &SceneLayout,
+        is_day: bool,
+        snow_depth: f32,
+    ) -> io::Result<()> {
+        let palette = renderer.palette(is_day);
+        // Level 0 runs along the top of the lawn row
+        let base_y = layout.horizon_y.saturating_sub(1);
+
+        for x in 0..layout.width {
+            if layout.is_house_column(x) {
+                continue;
+            }
+            let (here, next) = (level(x), level(x + 1));
+            let (ch, level) = match here.cmp(&next) {
+                std::cmp::Ordering::Less => ('/', here),
+                std::cmp::Ordering::Greater => ('\\', next),
+                std::cmp::Ordering::Equal if here > 0 => ('_', here),
+                std::cmp::Ordering::Equal => continue,
+            };
+            let Some(y) = base_y.checked_sub(level) else {
+                continue;
+            };
+            let color = if is_covered(x as usize, y as usize, snow_depth * 1.5) {
+                palette.ground.snow
+            } else {
+                palette.ground.foliage
+            };
+            renderer.render_char(x, y, ch, color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Height of the hills at column `x`, in whole rows. Two slow waves keep the
+/// slope under one row per column, so the outline never breaks.
+fn level(x: u16) -> u16 {
+    let x = x as f32;
+    let wave = 0.5 + 0.3 * (x / 9.0).sin() + 0.2 * (x / 4.7 + 1.3).sin();
+    (wave * MAX_HEIGHT).round().max(0.0) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outline_is_continuous() {
+        for x in 0..500 {
+            assert!(level(x) <= MAX_HEIGHT as u16);
+            assert!(level(x).abs_diff(level(x + 1)) <= 1);
+        }
+        assert!((0..500).any(|x| level(x) == 0));
+        assert!((0..500).any(|x| level(x) == MAX_HEIGHT as u16));
+    }
+}
@@ -1,18 +1,8 @@
+use super::snow_cover::is_covered;
 use crate::render::TerminalRenderer;
 use crossterm::style::Color;
 use std::io;
 
-const WOOD_COLOR: Color = Color::Rgb {
-    r: 210,
-    g: 180,
-    b: 140,
-};
-const DOOR_COLOR: Color = Color::Rgb {
-    r: 139,
-    g: 69,
-    b: 19,
-};
-
 #[derive(Default)]
 pub struct House;
 
@@ -20,14 +10,10 @@ impl House {
     pub const WIDTH: u16 = 64;
     pub const HEIGHT: u16 = 13;
     pub const CHIMNEY_X_OFFSET: u16 = 10;
-
-    pub fn height(&self) -> u16 {
-        Self::HEIGHT
-    }
-
-    pub fn width(&self) -> u16 {
-        Self::WIDTH
-    }
+    /// Row of the `~~~` roof edge, and where it starts and how wide it is.
+    pub const EAVE_Y_OFFSET: u16 = 7;
+    pub const EAVE_X_OFFSET: u16 = 3;
+    pub const EAVE_WIDTH: u16 = 29;
 
     pub fn get_ascii(&self) -> Vec<&'static str> {
         vec![
@@ -53,103 +39,211 @@ impl House {
         x: u16,
         y: u16,
         is_day: bool,
+        snow_depth: f32,
+        windows_glow: bool,
     ) -> io::Result<()> {
-        let ascii = self.get_ascii();
+        let shape = Shape {
+            ascii: self.get_ascii(),
+            roof_row: 4,
+            eave_row: Self::EAVE_Y_OFFSET as usize,
+        };
+        shape.render(renderer, x, y, is_day, snow_depth, windows_glow)
+    }
+}
 
-        let wood_color = if is_day {
-            WOOD_COLOR
-        } else {
-            Color::Rgb {
-                r: 100,
-                g: 70,
-                b: 50,
-            }
+/// A small one-storey cottage for narrow or short terminals, drawn in the same
+/// colors as the house.
+#[derive(Default)]
+pub struct Cottage;
+
+impl Cottage {
+    pub const WIDTH: u16 = 24;
+    pub const HEIGHT: u16 = 7;
+    pub const CHIMNEY_X_OFFSET: u16 = 7;
+    pub const EAVE_Y_OFFSET: u16 = 3;
+    pub const EAVE_X_OFFSET: u16 = 3;
+    pub const EAVE_WIDTH: u16 = 20;
+
+    pub fn get_ascii(&self) -> Vec<&'static str> {
+        vec![
+            "       _                ",
+            "      |_|_.-'`-._       ",
+            "     _.-'~_-~_-~_`-._   ",
+            "   ~~~~~~~~~~~~~~~~~~~~ ",
+            "    |  []   |.|   [] |  ",
+            "   =|_______|_|______|= ",
+            " ^^^^^^^^^^ === ^^^^^^^ ",
+        ]
+    }
+
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        is_day: bool,
+        snow_depth: f32,
+        windows_glow: bool,
+    ) -> io::Result<()> {
+        let shape = Shape {
+            ascii: self.get_ascii(),
+            roof_row: 1,
+            eave_row: Self::EAVE_Y_OFFSET as usize,
         };
-        let roof_color = if is_day {
-            Color::DarkRed
+        shape.render(renderer, x, y, is_day, snow_depth, windows_glow)
+    }
+}
+
+/// Which house sprite the scene layout places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HouseStyle {
+    House,
+    Cottage,
+}
+
+impl HouseStyle {
+    pub fn width(self) -> u16 {
+        match self {
+            HouseStyle::House => House::WIDTH,
+            HouseStyle::Cottage => Cottage::WIDTH,
+        }
+    }
+
+    pub fn height(self) -> u16 {
+        match self {
+            HouseStyle::House => House::HEIGHT,
+            HouseStyle::Cottage => Cottage::HEIGHT,
+        }
+    }
+
+    pub fn chimney_x_offset(self) -> u16 {
+        match self {
+            HouseStyle::House => House::CHIMNEY_X_OFFSET,
+            HouseStyle::Cottage => Cottage::CHIMNEY_X_OFFSET,
+        }
+    }
+
+    /// Where the roof edge starts, as (x offset, y offset), and how wide it is.
+    pub fn eave(self) -> (u16, u16, u16) {
+        match self {
+            HouseStyle::House => (
+                House::EAVE_X_OFFSET,
+                House::EAVE_Y_OFFSET,
+                House::EAVE_WIDTH,
+            ),
+            HouseStyle::Cottage => (
+                Cottage::EAVE_X_OFFSET,
+                Cottage::EAVE_Y_OFFSET,
+                Cottage::EAVE_WIDTH,
+            ),
+        }
+    }
+
+    pub fn render(
+        self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        is_day: bool,
+        snow_depth: f32,
+        windows_glow: bool,
+    ) -> io::Result<()> {
+        match self {
+            HouseStyle::House => House.render(renderer, x, y, is_day, snow_depth, windows_glow),
+            HouseStyle::Cottage => Cottage.render(renderer, x, y, is_day, snow_depth, windows_glow),
+        }
+    }
+}
+
+/// A house sprite split into bands: chimney rows above `roof_row`, the roof
+/// down to the `~~~` edge at `eave_row`, then walls, the base and the lawn
+/// strip as its last two rows.
+struct Shape {
+    ascii: Vec<&'static str>,
+    roof_row: usize,
+    eave_row: usize,
+}
+
+impl Shape {
+    fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        x: u16,
+        y: u16,
+        is_day: bool,
+        snow_depth: f32,
+        windows_glow: bool,
+    ) -> io::Result<()> {
+        let palette = renderer.palette(is_day);
+        let colors = palette.house;
+        let window_color = if windows_glow {
+            colors.window_glow
         } else {
-            Color::DarkMagenta
+            colors.window
+        };
+        let snow_color = palette.ground.snow;
+        let (roof_row, eave_row) = (self.roof_row, self.eave_row);
+        let base_row = self.ascii.len() - 2;
+        // The roof edge whitens first, then snow creeps up the slope
+        let roof_snow = |row: usize, col: usize| {
+            if row == eave_row {
+                is_covered(col, row, snow_depth * 2.0)
+            } else if (roof_row..eave_row).contains(&row) {
+                is_covered(col, row, snow_depth * 1.5 - (eave_row - row) as f32 * 0.2)
+            } else {
+                false
+            }
         };
-        let window_color = if is_day { Color::Cyan } else { Color::Yellow };
 
-        for (i, line) in ascii.iter().enumerate() {
+        for (i, line) in self.ascii.iter().enumerate() {
             let row = y + i as u16;
 
-            match i {
-                0..=6 => {
-                    for (j, ch) in line.chars().enumerate() {
-                        if ch != ' ' {
-                            let col = x + j as u16;
-                            let color = if i < 4 && (ch == '(' || ch == ')' || ch == '_') {
-                                Color::DarkGrey
-                            } else if i < 4 {
-                                Color::Grey
-                            } else {
-                                roof_color
-                            };
-                            renderer.render_char(col, row, ch, color)?;
-                        }
-                    }
-                }
-                7 => {
-                    renderer.render_line_colored(x, row, line, roof_color)?;
+            for (j, ch) in line.chars().enumerate() {
+                if ch == ' ' {
+                    continue;
                 }
-                8..=10 => {
-                    for (j, ch) in line.chars().enumerate() {
-                        if ch != ' ' {
-                            let col = x + j as u16;
-                            let color = if ch == '[' || ch == ']' {
-                                window_color
-                            } else if ch == '|' || ch == '.' || ch == '_' {
-                                wood_color
-                            } else if ch == '(' || ch == ')' {
-                                DOOR_COLOR
-                            } else if ch == '=' {
-                                Color::DarkGrey
-                            } else {
-                                wood_color
-                            };
-                            renderer.render_char(col, row, ch, color)?;
-                        }
-                    }
+                let col = x + j as u16;
+
+                if i <= eave_row {
+                    let color = if roof_snow(i, j) {
+                        snow_color
+                    } else if i == eave_row || i >= roof_row {
+                        colors.roof
+                    } else if ch == '(' || ch == ')' || ch == '_' {
+                        colors.trim
+                    } else {
+                        colors.chimney
+                    };
+                    renderer.render_char(col, row, ch, color)?;
+                    continue;
                 }
-                11 => {
-                    for (j, ch) in line.chars().enumerate() {
-                        if ch != ' ' {
-                            let col = x + j as u16;
-                            let color = if ch == '=' || ch == '|' {
-                                Color::DarkGrey
-                            } else if ch == '(' || ch == ')' {
-                                DOOR_COLOR
-                            } else {
-                                wood_color
-                            };
-                            renderer.render_char(col, row, ch, color)?;
-                        }
+
+                let color = if i < base_row {
+                    if ch == '[' || ch == ']' {
+                        window_color
+                    } else if ch == '(' || ch == ')' {
+                        colors.door
+                    } else if ch == '=' {
+                        colors.trim
+                    } else {
+                        colors.wall
                     }
-                }
-                12 => {
-                    for (j, ch) in line.chars().enumerate() {
-                        if ch != ' ' {
-                            let col = x + j as u16;
-                            let color = if ch == '^' {
-                                if is_day {
-                                    Color::Green
-                                } else {
-                                    Color::DarkGreen
-                                }
-                            } else if ch == '=' {
-                                Color::DarkGrey
-                            } else {
-                                Color::Reset
-                            };
-                            renderer.render_char(col, row, ch, color)?;
-                        }
+                } else if i == base_row {
+                    if ch == '=' || ch == '|' {
+                        colors.trim
+                    } else if ch == '(' || ch == ')' {
+                        colors.door
+                    } else {
+                        colors.wall
                     }
-                }
-                _ => {
-                    renderer.render_line_colored(x, row, line, Color::Yellow)?;
-                }
+                } else if ch == '^' {
+                    palette.ground.grass
+                } else if ch == '=' {
+                    colors.trim
+                } else {
+                    Color::Reset
+                };
+                renderer.render_char(col, row, renderer.line_char(ch), color)?;
             }
         }
         Ok(())
@@ -0,0 +1,236 @@
+//! Where the scene's pieces go for a terminal size. Small terminals, like split
+//! panes, get a cottage on a thinner strip of ground with nothing around it;
+//! wide ones get more trees, hills and a second house to fill the space.
+
+use super::WorldScene;
+use super::house::HouseStyle;
+
+/// Below this width or height the full house does not fit.
+pub const NORMAL_MIN_WIDTH: u16 = 70;
+pub const NORMAL_MIN_HEIGHT: u16 = 20;
+/// Beyond this width there is room for extra scenery.
+pub const WIDE_MIN_WIDTH: u16 = 121;
+
+const COMPACT_MIN_GROUND_HEIGHT: u16 = 3;
+
+/// Columns taken by a pine tree, and the distance between neighbouring ones.
+const PINE_WIDTH: u16 = 9;
+const PINE_SPACING: u16 = 14;
+/// Gap from the house to the first pine on its right, past the fence.
+const FIRST_PINE_GAP: u16 = 18;
+/// Gap from the house to the nearest extra pine on its left, past the tree and mailbox.
+const LEFT_PINE_GAP: u16 = 44;
+/// Space kept either side of the second house.
+const SECOND_HOUSE_GAP: u16 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutTier {
+    Compact,
+    Normal,
+    Wide,
+}
+
+impl LayoutTier {
+    pub fn for_size(width: u16, height: u16) -> Self {
+        if width < NORMAL_MIN_WIDTH || height < NORMAL_MIN_HEIGHT {
+            LayoutTier::Compact
+        } else if width >= WIDE_MIN_WIDTH {
+            LayoutTier::Wide
+        } else {
+            LayoutTier::Normal
+        }
+    }
+}
+
+/// A house sprite and its top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HousePlacement {
+    pub style: HouseStyle,
+    pub x: u16,
+    pub y: u16,
+}
+
+impl HousePlacement {
+    /// Where the smoke rises from.
+    pub fn chimney(&self) -> (u16, u16) {
+        (self.x + self.style.chimney_x_offset(), self.y)
+    }
+
+    /// Where the roof edge starts, and how wide it is.
+    pub fn eave(&self) -> (u16, u16, u16) {
+        let (x_offset, y_offset, width) = self.style.eave();
+        (self.x + x_offset, self.y + y_offset, width)
+    }
+
+    /// Columns the sprite covers, end exclusive.
+    pub fn columns(&self) -> (u16, u16) {
+        (self.x, self.x + self.style.width())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SceneLayout {
+    pub tier: LayoutTier,
+    pub width: u16,
+    pub height: u16,
+    pub ground_height: u16,
+    pub horizon_y: u16,
+    /// The house in the middle, with smoke and icicles.
+    pub house: HousePlacement,
+    pub second_house: Option<HousePlacement>,
+    /// Left edges of pine trees standing on the horizon.
+    pub pines: Vec<u16>,
+    pub hills: bool,
+    /// Whether the tree, fence and mailbox beside the house are drawn.
+    pub decorations: bool,
+}
+
+impl SceneLayout {
+    pub fn new(width: u16, height: u16) -> Self {
+        let tier = LayoutTier::for_size(width, height);
+        let (style, ground_height) = match tier {
+            LayoutTier::Compact => (
+                HouseStyle::Cottage,
+                (height / 4).clamp(COMPACT_MIN_GROUND_HEIGHT, WorldScene::GROUND_HEIGHT),
+            ),
+            LayoutTier::Normal | LayoutTier::Wide => (HouseStyle::House, WorldScene::GROUND_HEIGHT),
+        };
+        let horizon_y = height.saturating_sub(ground_height);
+        let house = HousePlacement {
+            style,
+            x: (width / 2).saturating_sub(style.width() / 2),
+            y: horizon_y.saturating_sub(style.height()),
+        };
+
+        let mut layout = Self {
+            tier,
+            width,
+            height,
+            ground_height,
+            horizon_y,
+            house,
+            second_house: None,
+            pines: Vec::new(),
+            hills: tier == LayoutTier::Wide,
+            decorations: tier != LayoutTier::Compact,
+        };
+        if tier == LayoutTier::Wide {
+            layout.add_scenery();
+        }
+        layout
+    }
+
+    /// Fills the space either side of the house: pines and a cottage to the
+    /// right, and pines to the left beyond the tree and mailbox.
+    fn add_scenery(&mut self) {
+        let fits = |x: u16, width: u16| x + width < self.width;
+
+        let (_, house_end) = self.house.columns();
+        let mut x = house_end + FIRST_PINE_GAP;
+        if fits(x, PINE_WIDTH) {
+            self.pines.push(x);
+            x += PINE_WIDTH + SECOND_HOUSE_GAP;
+
+            let cottage = HouseStyle::Cottage;
+            if fits(x, cottage.width()) {
+                self.second_house = Some(HousePlacement {
+                    style: cottage,
+                    x,
+                    y: self.horizon_y.saturating_sub(cottage.height()),
+                });
+                x += cottage.width() + SECOND_HOUSE_GAP;
+            }
+            while fits(x, PINE_WIDTH) {
+                self.pines.push(x);
+                x += PINE_SPACING;
+            }
+        }
+
+        let mut left = self.house.x.checked_sub(LEFT_PINE_GAP);
+        while let Some(x) = left.filter(|&x| x > 0) {
+            self.pines.push(x);
+            left = x.checked_sub(PINE_SPACING);
+        }
+    }
+
+    /// The house in the middle, then the second house if there is one.
+    pub fn houses(&self) -> impl Iterator<Item = HousePlacement> {
+        [Some(self.house), self.second_house].into_iter().flatten()
+    }
+
+    /// Whether column `x` is covered by one of the houses.
+    pub fn is_house_column(&self, x: u16) -> bool {
+        self.houses().any(|house| {
+            let (start, end) = house.columns();
+            (start..end).contains(&x)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiers() {
+        assert_eq!(LayoutTier::for_size(40, 12), LayoutTier::Compact);
+        assert_eq!(LayoutTier::for_size(200, 15), LayoutTier::Compact);
+        assert_eq!(LayoutTier::for_size(80, 24), LayoutTier::Normal);
+        assert_eq!(LayoutTier::for_size(120, 40), LayoutTier::Normal);
+        assert_eq!(LayoutTier::for_size(121, 40), LayoutTier::Wide);
+    }
+
+    #[test]
+    fn test_normal_layout_matches_the_classic_scene() {
+        let layout = SceneLayout::new(80, 24);
+        assert_eq!(layout.horizon_y, 17);
+        assert_eq!(
+            layout.house,
+            HousePlacement {
+                style: HouseStyle::House,
+                x: 8,
+                y: 4,
+            }
+        );
+        assert_eq!(layout.house.chimney(), (18, 4));
+        assert_eq!(layout.house.eave(), (11, 11, 29));
+        assert!(layout.decorations);
+        assert!(!layout.hills);
+        assert!(layout.pines.is_empty());
+        assert_eq!(layout.second_house, None);
+    }
+
+    #[test]
+    fn test_compact_layout_fits_a_split_pane() {
+        let layout = SceneLayout::new(40, 12);
+        assert_eq!(layout.tier, LayoutTier::Compact);
+        assert_eq!(layout.house.style, HouseStyle::Cottage);
+        assert_eq!(layout.ground_height, 3);
+        assert_eq!(layout.house.x, 8);
+        assert_eq!(
+            layout.house.y + HouseStyle::Cottage.height(),
+            layout.horizon_y
+        );
+        assert!(!layout.decorations);
+        assert!(layout.pines.is_empty());
+    }
+
+    #[test]
+    fn test_wide_layout_adds_scenery_that_fits() {
+        // Just wide enough for the pine that used to appear past 120 columns
+        let layout = SceneLayout::new(130, 40);
+        assert_eq!(layout.pines, vec![115]);
+        assert_eq!(layout.second_house, None);
+        assert!(layout.hills);
+
+        let layout = SceneLayout::new(240, 60);
+        let second = layout.second_house.unwrap();
+        assert_eq!(second.style, HouseStyle::Cottage);
+        assert_eq!(second.y + second.style.height(), layout.horizon_y);
+        assert!(layout.pines.len() > 2);
+        for &x in &layout.pines {
+            assert!(x > 0 && x + PINE_WIDTH < layout.width);
+            assert!((x..x + PINE_WIDTH).all(|col| !layout.is_house_column(col)));
+        }
+    }
+}
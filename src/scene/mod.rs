@@ -1,81 +1,244 @@
 pub mod decorations;
 pub mod ground;
+pub mod hills;
 pub mod house;
+pub mod layout;
+pub mod sky;
+pub mod snow_cover;
+pub mod wetness;
 
+use crate::history::HistoryRecord;
 use crate::render::TerminalRenderer;
-use crate::weather::WeatherConditions;
+use crate::season::Season;
+use crate::weather::{WeatherConditions, WeatherData};
 use std::io;
+use std::time::{Duration, Instant};
+
+/// Longest gap between history records that still counts as continuous weather.
+const MAX_HISTORY_GAP: Duration = Duration::from_secs(3 * 3600);
+/// Longest step a single frame may take, like the effect fades.
+const MAX_STEP: Duration = Duration::from_millis(100);
+/// Temperature (°C) at which the whole lawn is frosted over; frost starts at 0°C.
+const HARD_FROST_TEMPERATURE: f64 = -6.0;
 
 pub struct WorldScene {
-    house: house::House,
+    layout: layout::SceneLayout,
     ground: ground::Ground,
+    hills: hills::Hills,
     decorations: decorations::Decorations,
-    width: u16,
-    height: u16,
+    snow_cover: snow_cover::SnowCover,
+    wetness: wetness::Wetness,
+    last_update: Option<Instant>,
+    temperature: Option<f64>,
+    cloud_cover: f64,
+    season: Season,
 }
 
 impl WorldScene {
+    /// Rows of ground below the horizon, except in compact layouts.
     pub const GROUND_HEIGHT: u16 = 7;
 
     pub fn new(width: u16, height: u16) -> Self {
-        let house = house::House;
         let ground = ground::Ground;
         let decorations = decorations::Decorations::new();
 
         Self {
-            house,
+            layout: layout::SceneLayout::new(width, height),
             ground,
+            hills: hills::Hills,
             decorations,
-            width,
-            height,
+            snow_cover: snow_cover::SnowCover::default(),
+            wetness: wetness::Wetness::default(),
+            last_update: None,
+            temperature: None,
+            cloud_cover: 0.0,
+            season: Season::Summer,
         }
     }
 
     pub fn update_size(&mut self, width: u16, height: u16) {
-        self.width = width;
-        self.height = height;
+        if (width, height) != (self.layout.width, self.layout.height) {
+            self.layout = layout::SceneLayout::new(width, height);
+        }
     }
 
-    pub fn render(
+    pub fn set_season(&mut self, season: Season) {
+        self.season = season;
+    }
+
+    /// Lets snow pile up or melt and the ground soak or dry according to the
+    /// current weather.
+    pub fn update(&mut self, weather: Option<&WeatherData>) {
+        let now = Instant::now();
+        let elapsed = self
+            .last_update
+            .map_or(Duration::ZERO, |last| now.duration_since(last));
+        self.last_update = Some(now);
+
+        if let Some(weather) = weather {
+            self.advance(weather, elapsed.min(MAX_STEP));
+            self.temperature = Some(weather.temperature);
+            self.cloud_cover = weather.cloud_cover;
+        }
+    }
+
+    /// Starts with the snow and wet ground left by recently recorded weather.
+    pub fn seed_from_history(&mut self, records: &[HistoryRecord]) {
+        for pair in records.windows(2) {
+            let elapsed = pair[1].recorded_at.saturating_sub(pair[0].recorded_at);
+            self.advance(
+                &pair[0].data,
+                Duration::from_secs(elapsed).min(MAX_HISTORY_GAP),
+            );
+        }
+    }
+
+    fn advance(&mut self, weather: &WeatherData, elapsed: Duration) {
+        self.snow_cover.advance(weather, elapsed);
+        self.wetness.advance(weather, elapsed);
+    }
+
+    /// How wet the ground is, for rain to ripple the puddles.
+    pub fn wetness(&self) -> f32 {
+        self.wetness.level()
+    }
+
+    /// Paints the sky above the horizon as a background gradient for the
+    /// time of day (`hour` of the local clock, or just day and night without
+    /// one) and the weather.
+    pub fn render_sky(
         &self,
         renderer: &mut TerminalRenderer,
         conditions: &WeatherConditions,
+        hour: Option<u32>,
     ) -> io::Result<()> {
-        let horizon_y = self.height.saturating_sub(Self::GROUND_HEIGHT);
+        let horizon_y = self.layout.horizon_y;
+        let is_precipitating = conditions.is_raining || conditions.is_snowing || conditions.is_foggy;
+        let gradient = sky::SkyGradient::new(
+            &renderer.palette(conditions.is_day).sky,
+            hour.map_or(sky::SkyPhase::from_is_day(conditions.is_day), |hour| {
+                sky::SkyPhase::at(conditions.is_day, hour)
+            }),
+            sky::overcast_level(self.cloud_cover, is_precipitating),
+            conditions.is_thunderstorm,
+        );
 
-        // House position
-        let house_width = self.house.width();
-        let house_height = self.house.height();
-        let house_x = (self.width / 2).saturating_sub(house_width / 2);
-        let house_y = horizon_y.saturating_sub(house_height);
+        for y in 0..horizon_y {
+            renderer.fill_background(y, gradient.color_at(y, horizon_y))?;
+        }
+        Ok(())
+    }
 
-        // Door/Path alignment
+    pub fn render(
+        &self,
+        renderer: &mut TerminalRenderer,
+        conditions: &WeatherConditions,
+    ) -> io::Result<()> {
+        let layout = &self.layout;
+        let horizon_y = layout.horizon_y;
+        let snow_depth = self.snow_cover.depth();
+        let temperature = self.temperature.unwrap_or(f64::MAX);
+        let frost = (temperature / HARD_FROST_TEMPERATURE).clamp(0.0, 1.0) as f32;
 
         // Render Ground
         self.ground.render(
             renderer,
-            self.width,
-            Self::GROUND_HEIGHT,
-            horizon_y,
-            conditions.is_day,
+            &ground::GroundRenderConfig {
+                width: layout.width,
+                height: layout.ground_height,
+                y_start: horizon_y,
+                is_day: conditions.is_day,
+                snow_depth,
+                wetness: self.wetness.level(),
+                frost,
+                season: self.season,
+            },
         )?;
 
-        // Render House
-        self.house
-            .render(renderer, house_x, house_y, conditions.is_day)?;
+        if layout.hills {
+            self.hills
+                .render(renderer, layout, conditions.is_day, snow_depth)?;
+        }
+
+        // Render Houses
+        // Lights stay on against the cold, even by day
+        for house in layout.houses() {
+            house.style.render(
+                renderer,
+                house.x,
+                house.y,
+                conditions.is_day,
+                snow_depth,
+                temperature < 0.0,
+            )?;
+        }
 
         // Render Decorations
-        self.decorations.render(
-            renderer,
-            &crate::scene::decorations::DecorationRenderConfig {
-                horizon_y,
-                house_x,
-                house_width,
-                width: self.width,
-                is_day: conditions.is_day,
-            },
-        )?;
+        let config = decorations::DecorationRenderConfig {
+            horizon_y,
+            house_x: layout.house.x,
+            house_width: layout.house.style.width(),
+            width: layout.width,
+            is_day: conditions.is_day,
+            snow_depth,
+            season: self.season,
+        };
+        if layout.decorations {
+            self.decorations.render(renderer, &config)?;
+        }
+        self.decorations
+            .render_pines(renderer, &layout.pines, &config)?;
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn record(recorded_at: u64, condition: WeatherCondition, temperature: f64) -> HistoryRecord {
+        HistoryRecord {
+            recorded_at,
+            data: WeatherData {
+                condition,
+                temperature,
+                apparent_temperature: temperature,
+                humidity: 90.0,
+                precipitation: 0.0,
+                wind_speed: 5.0,
+                wind_direction: 0.0,
+                cloud_cover: 100.0,
+                pressure: 1010.0,
+                visibility: None,
+                is_day: true,
+                moon_phase: None,
+                timestamp: "2024-01-10T08:00".to_string(),
+                precipitation_level: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_seed_from_history() {
+        let mut snowy = WorldScene::new(80, 24);
+        snowy.seed_from_history(&[
+            record(0, WeatherCondition::Snow, -2.0),
+            record(1800, WeatherCondition::Cloudy, -1.0),
+            record(3600, WeatherCondition::Cloudy, -1.0),
+        ]);
+        assert_eq!(snowy.snow_cover.depth(), 1.0);
+        assert_eq!(snowy.wetness(), 0.0);
+
+        // A shower that ended a few minutes ago still leaves the ground wet
+        let mut rainy = WorldScene::new(80, 24);
+        rainy.seed_from_history(&[
+            record(0, WeatherCondition::Rain, 12.0),
+            record(1800, WeatherCondition::Cloudy, 12.0),
+            record(2100, WeatherCondition::Cloudy, 12.0),
+        ]);
+        assert!(rainy.wetness() > 0.5);
+        assert_eq!(rainy.snow_cover.depth(), 0.0);
+    }
+}
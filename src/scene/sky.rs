@@ -0,0 +1,180 @@
+use crate::render::color_rgb;
+use crate::theme::SkyColors;
+use crossterm::style::Color;
+use std::ops::Range;
+
+type Rgb = (u8, u8, u8);
+
+/// Daylight hours around sunrise and sunset when the sky turns orange.
+const DAWN_HOURS: Range<u32> = 5..8;
+const DUSK_HOURS: Range<u32> = 17..21;
+/// Cloud cover (percent) at which the sky starts to grey over, and where it is fully grey.
+const GREYING_COVER: Range<f64> = 50.0..100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkyPhase {
+    Day,
+    Dusk,
+    Night,
+}
+
+impl SkyPhase {
+    pub fn at(is_day: bool, hour: u32) -> Self {
+        if !is_day {
+            Self::Night
+        } else if DAWN_HOURS.contains(&hour) || DUSK_HOURS.contains(&hour) {
+            Self::Dusk
+        } else {
+            Self::Day
+        }
+    }
+
+    /// Day or night alone, when there is no clock to tell dawn and dusk by.
+    pub fn from_is_day(is_day: bool) -> Self {
+        if is_day { Self::Day } else { Self::Night }
+    }
+
+    /// Zenith and horizon colors of a clear sky.
+    fn clear(self, colors: &SkyColors) -> (Color, Color) {
+        match self {
+            Self::Dusk => (colors.dusk_top, colors.dusk_horizon),
+            Self::Day | Self::Night => (colors.top, colors.horizon),
+        }
+    }
+}
+
+/// How grey the sky is, from 0.0 (clear) to 1.0 (fully overcast).
+pub fn overcast_level(cloud_cover: f64, is_precipitating: bool) -> f32 {
+    if is_precipitating {
+        return 1.0;
+    }
+    ((cloud_cover - GREYING_COVER.start) / (GREYING_COVER.end - GREYING_COVER.start))
+        .clamp(0.0, 1.0) as f32
+}
+
+/// A vertical sky gradient, from the zenith at the top row down to the horizon.
+#[derive(Debug, Clone, Copy)]
+pub struct SkyGradient {
+    top: Rgb,
+    horizon: Rgb,
+}
+
+impl SkyGradient {
+    /// Takes its colors from the sky palette for the time of day, so `colors`
+    /// should be the night palette when `phase` is `Night`.
+    pub fn new(colors: &SkyColors, phase: SkyPhase, overcast: f32, is_storm: bool) -> Self {
+        let (top, horizon) = if is_storm {
+            (rgb_of(colors.storm_top), rgb_of(colors.storm_horizon))
+        } else {
+            let (clear_top, clear_horizon) = phase.clear(colors);
+            (
+                lerp(rgb_of(clear_top), rgb_of(colors.overcast_top), overcast),
+                lerp(
+                    rgb_of(clear_horizon),
+                    rgb_of(colors.overcast_horizon),
+                    overcast,
+                ),
+            )
+        };
+        Self { top, horizon }
+    }
+
+    /// Color of row `y` in a sky `height` rows tall.
+    pub fn color_at(&self, y: u16, height: u16) -> Color {
+        let t = if height > 1 {
+            y as f32 / (height - 1) as f32
+        } else {
+            1.0
+        };
+        let (r, g, b) = lerp(self.top, self.horizon, t);
+        Color::Rgb { r, g, b }
+    }
+}
+
+/// Named colors are mixed as xterm shows them.
+fn rgb_of(color: Color) -> Rgb {
+    color_rgb(color).unwrap_or((0, 0, 0))
+}
+
+fn lerp(from: Rgb, to: Rgb, t: f32) -> Rgb {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t.clamp(0.0, 1.0)).round() as u8;
+    (mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+
+    fn gradient(phase: SkyPhase, overcast: f32, is_storm: bool) -> SkyGradient {
+        let colors = Theme::default().palette(phase != SkyPhase::Night).sky;
+        SkyGradient::new(&colors, phase, overcast, is_storm)
+    }
+
+    fn rgb(color: Color) -> Rgb {
+        match color {
+            Color::Rgb { r, g, b } => (r, g, b),
+            other => panic!("expected an RGB color, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sky_phase() {
+        assert_eq!(SkyPhase::at(true, 12), SkyPhase::Day);
+        assert_eq!(SkyPhase::at(true, 6), SkyPhase::Dusk);
+        assert_eq!(SkyPhase::at(true, 19), SkyPhase::Dusk);
+        assert_eq!(SkyPhase::at(false, 19), SkyPhase::Night);
+        assert_eq!(SkyPhase::from_is_day(true), SkyPhase::Day);
+        assert_eq!(SkyPhase::from_is_day(false), SkyPhase::Night);
+    }
+
+    #[test]
+    fn test_gradient_runs_from_zenith_to_horizon() {
+        let sky = gradient(SkyPhase::Day, 0.0, false);
+        assert_eq!(rgb(sky.color_at(0, 20)), (30, 90, 200));
+        assert_eq!(rgb(sky.color_at(19, 20)), (140, 190, 240));
+
+        // Dusk glows orange low down, night stays near black
+        let (r, _, b) = rgb(gradient(SkyPhase::Dusk, 0.0, false).color_at(19, 20));
+        assert!(r > b);
+        let (r, g, b) = rgb(gradient(SkyPhase::Night, 0.0, false).color_at(0, 20));
+        assert!(r.max(g).max(b) < 20);
+    }
+
+    #[test]
+    fn test_gradient_follows_the_theme() {
+        let nord = Theme::load_from(&crate::theme::ThemeSetting::Name("nord".into()), None)
+            .unwrap()
+            .day
+            .sky;
+        let sky = SkyGradient::new(&nord, SkyPhase::Day, 0.0, false);
+        assert_eq!(rgb(sky.color_at(0, 20)), (0x5e, 0x81, 0xac));
+        assert_ne!(
+            rgb(sky.color_at(0, 20)),
+            rgb(gradient(SkyPhase::Day, 0.0, false).color_at(0, 20))
+        );
+
+        // Named colors mix as xterm shows them
+        let colors = SkyColors {
+            top: Color::Black,
+            horizon: Color::White,
+            ..nord
+        };
+        let sky = SkyGradient::new(&colors, SkyPhase::Day, 0.0, false);
+        assert_eq!(rgb(sky.color_at(0, 3)), (0, 0, 0));
+        assert_eq!(rgb(sky.color_at(2, 3)), (255, 255, 255));
+    }
+
+    #[test]
+    fn test_overcast_and_storm_skies() {
+        assert_eq!(overcast_level(30.0, false), 0.0);
+        assert_eq!(overcast_level(75.0, false), 0.5);
+        assert_eq!(overcast_level(10.0, true), 1.0);
+
+        let (r, g, b) = rgb(gradient(SkyPhase::Day, 1.0, false).color_at(0, 20));
+        assert!(r.abs_diff(b) < 20 && g.abs_diff(b) < 20);
+
+        let (r, g, b) = rgb(gradient(SkyPhase::Day, 0.0, true).color_at(0, 20));
+        assert!(g > r && g > b);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::weather::WeatherData;
+use std::time::Duration;
+
+/// Depth gained per second of full-strength snowfall; a heavy fall covers
+/// everything in about ten minutes.
+const ACCUMULATION_PER_SEC: f32 = 1.0 / 600.0;
+/// Depth lost per second for each degree above freezing.
+const MELT_PER_DEGREE_SEC: f32 = 1.0 / 3600.0;
+
+/// Snow lying on the ground, roof and decorations, from 0.0 (none) to 1.0
+/// (fully covered). Builds up while it snows and melts above 0°C.
+#[derive(Debug, Clone, Default)]
+pub struct SnowCover {
+    depth: f32,
+}
+
+impl SnowCover {
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    pub fn advance(&mut self, weather: &WeatherData, elapsed: Duration) {
+        let secs = elapsed.as_secs_f32();
+        if weather.condition.is_snowing() {
+            self.depth += weather.precipitation_strength() * ACCUMULATION_PER_SEC * secs;
+        }
+        if weather.temperature > 0.0 {
+            self.depth -= weather.temperature as f32 * MELT_PER_DEGREE_SEC * secs;
+        }
+        self.depth = self.depth.clamp(0.0, 1.0);
+    }
+}
+
+/// Whether the cell at (`x`, `y`) is snowed over when `coverage` (0.0 to 1.0)
+/// of a surface is. The same cells fill first every frame, so cover grows in
+/// place instead of flickering.
+pub fn is_covered(x: usize, y: usize, coverage: f32) -> bool {
+    let hash = (x as u32 ^ 0x9E37).wrapping_mul(0x85EB_CA6B) ^ (y as u32).wrapping_mul(0xC2B2_AE35);
+    ((hash >> 8) % 100) < (coverage.clamp(0.0, 1.0) * 100.0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn weather(condition: WeatherCondition, temperature: f64) -> WeatherData {
+        WeatherData {
+            condition,
+            temperature,
+            apparent_temperature: temperature,
+            humidity: 90.0,
+            precipitation: 0.0,
+            wind_speed: 5.0,
+            wind_direction: 0.0,
+            cloud_cover: 100.0,
+            pressure: 1010.0,
+            visibility: None,
+            is_day: true,
+            moon_phase: None,
+            timestamp: "2024-01-10T08:00".to_string(),
+            precipitation_level: None,
+        }
+    }
+
+    #[test]
+    fn test_snow_builds_up_and_melts() {
+        let mut cover = SnowCover::default();
+        cover.advance(
+            &weather(WeatherCondition::Snow, -3.0),
+            Duration::from_secs(300),
+        );
+        let snowed = cover.depth();
+        assert!(snowed > 0.1);
+
+        // Frost keeps it, thaw melts it
+        cover.advance(
+            &weather(WeatherCondition::Clear, -5.0),
+            Duration::from_secs(3600),
+        );
+        assert_eq!(cover.depth(), snowed);
+        cover.advance(
+            &weather(WeatherCondition::Clear, 6.0),
+            Duration::from_secs(3600),
+        );
+        assert_eq!(cover.depth(), 0.0);
+    }
+
+    #[test]
+    fn test_coverage_grows_in_place() {
+        let cells = |coverage| {
+            (0..200)
+                .filter(|&x| is_covered(x, 0, coverage))
+                .collect::<Vec<_>>()
+        };
+        let light = cells(0.2);
+        let heavy = cells(0.8);
+        assert!(light.len() < heavy.len());
+        assert!(light.iter().all(|x| heavy.contains(x)));
+        assert!(cells(0.0).is_empty());
+        assert_eq!(cells(1.0).len(), 200);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::weather::WeatherData;
+use std::time::Duration;
+
+/// Wetness gained per second of full-strength rain; a downpour soaks the
+/// ground in about five minutes.
+const WETTING_PER_SEC: f32 = 1.0 / 300.0;
+/// Wetness lost per second once the rain stops, at 0°C. Warmer air dries faster.
+const DRYING_PER_SEC: f32 = 1.0 / 2400.0;
+/// Wetness at which puddles start to form.
+const PUDDLE_THRESHOLD: f32 = 0.25;
+/// Columns per stretch of ground that may hold one puddle.
+const PUDDLE_SPACING: usize = 12;
+/// Half-width of a puddle when the ground is soaked.
+const MAX_PUDDLE_HALF_WIDTH: f32 = 4.0;
+
+/// How wet the ground is, from 0.0 (dry) to 1.0 (soaked). Rises while it rains
+/// and dries out slowly afterwards, so puddles outlast the shower.
+#[derive(Debug, Clone, Default)]
+pub struct Wetness {
+    level: f32,
+}
+
+impl Wetness {
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    pub fn advance(&mut self, weather: &WeatherData, elapsed: Duration) {
+        let secs = elapsed.as_secs_f32();
+        if weather.condition.is_raining() {
+            self.level += weather.precipitation_strength() * WETTING_PER_SEC * secs;
+        } else {
+            let warmth = 1.0 + weather.temperature.max(0.0) as f32 / 10.0;
+            self.level -= DRYING_PER_SEC * warmth * secs;
+        }
+        self.level = self.level.clamp(0.0, 1.0);
+    }
+}
+
+/// Whether ground cell (`x`, `row`) holds water, `row` counting down from the
+/// grass line. Puddles sit at fixed spots in the soil and widen as it gets wetter.
+pub fn is_puddle(x: usize, row: usize, wetness: f32) -> bool {
+    if row == 0 || wetness < PUDDLE_THRESHOLD {
+        return false;
+    }
+
+    let stretch = x / PUDDLE_SPACING;
+    let hash = (stretch as u32 ^ 0x2545_F491).wrapping_mul(0x9E37_79B9) ^ (row as u32 * 0x51ED);
+    // Only some stretches of each row get a puddle
+    if (hash >> 4) % 3 != 0 {
+        return false;
+    }
+
+    let center = stretch * PUDDLE_SPACING + 3 + ((hash >> 12) as usize % (PUDDLE_SPACING - 6));
+    let growth = (wetness - PUDDLE_THRESHOLD) / (1.0 - PUDDLE_THRESHOLD);
+    let half_width = (growth * MAX_PUDDLE_HALF_WIDTH).round() as usize;
+    x.abs_diff(center) <= half_width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::WeatherCondition;
+
+    fn weather(condition: WeatherCondition, temperature: f64) -> WeatherData {
+        WeatherData {
+            condition,
+            temperature,
+            apparent_temperature: temperature,
+            humidity: 90.0,
+            precipitation: 0.0,
+            wind_speed: 5.0,
+            wind_direction: 0.0,
+            cloud_cover: 100.0,
+            pressure: 1010.0,
+            visibility: None,
+            is_day: true,
+            moon_phase: None,
+            timestamp: "2024-05-10T08:00".to_string(),
+            precipitation_level: None,
+        }
+    }
+
+    #[test]
+    fn test_ground_gets_wet_and_dries_faster_when_warm() {
+        let mut cool = Wetness::default();
+        cool.advance(
+            &weather(WeatherCondition::Rain, 10.0),
+            Duration::from_secs(900),
+        );
+        assert_eq!(cool.level(), 1.0);
+
+        let mut warm = cool.clone();
+        cool.advance(
+            &weather(WeatherCondition::Clear, 5.0),
+            Duration::from_secs(600),
+        );
+        warm.advance(
+            &weather(WeatherCondition::Clear, 25.0),
+            Duration::from_secs(600),
+        );
+        assert!(warm.level() < cool.level());
+        assert!(cool.level() > 0.0);
+
+        cool.advance(
+            &weather(WeatherCondition::Clear, 5.0),
+            Duration::from_secs(3 * 3600),
+        );
+        assert_eq!(cool.level(), 0.0);
+    }
+
+    #[test]
+    fn test_puddles_widen_with_wetness() {
+        let cells = |wetness| {
+            (0..240)
+                .flat_map(|x| (0..7).map(move |row| (x, row)))
+                .filter(|&(x, row)| is_puddle(x, row, wetness))
+                .count()
+        };
+        assert_eq!(cells(0.1), 0);
+        assert!(cells(0.4) > 0);
+        assert!(cells(0.4) < cells(1.0));
+        assert!((0..240).all(|x| !is_puddle(x, 0, 1.0)));
+    }
+}
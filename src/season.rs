@@ -0,0 +1,85 @@
+use chrono::{Datelike, Local, NaiveDate};
+use std::str::FromStr;
+
+/// Meteorological season, which decides how the tree and lawn look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// Season on `date` at `latitude`; the southern hemisphere is six months ahead.
+    pub fn from_date(date: NaiveDate, latitude: f64) -> Self {
+        let month = if latitude < 0.0 {
+            (date.month() + 5) % 12 + 1
+        } else {
+            date.month()
+        };
+
+        match month {
+            3..=5 => Self::Spring,
+            6..=8 => Self::Summer,
+            9..=11 => Self::Autumn,
+            _ => Self::Winter,
+        }
+    }
+
+    pub fn current(latitude: f64) -> Self {
+        Self::from_date(Local::now().date_naive(), latitude)
+    }
+}
+
+impl FromStr for Season {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "spring" => Ok(Self::Spring),
+            "summer" => Ok(Self::Summer),
+            "autumn" | "fall" => Ok(Self::Autumn),
+            "winter" => Ok(Self::Winter),
+            _ => Err(format!(
+                "Unknown season: '{}'. Valid options: spring, summer, autumn, winter",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_northern_hemisphere_seasons() {
+        let berlin = 52.5;
+        assert_eq!(Season::from_date(date(1, 15), berlin), Season::Winter);
+        assert_eq!(Season::from_date(date(3, 1), berlin), Season::Spring);
+        assert_eq!(Season::from_date(date(7, 4), berlin), Season::Summer);
+        assert_eq!(Season::from_date(date(10, 31), berlin), Season::Autumn);
+        assert_eq!(Season::from_date(date(12, 1), berlin), Season::Winter);
+    }
+
+    #[test]
+    fn test_southern_hemisphere_is_flipped() {
+        let sydney = -33.9;
+        assert_eq!(Season::from_date(date(1, 15), sydney), Season::Summer);
+        assert_eq!(Season::from_date(date(4, 20), sydney), Season::Autumn);
+        assert_eq!(Season::from_date(date(7, 4), sydney), Season::Winter);
+        assert_eq!(Season::from_date(date(10, 31), sydney), Season::Spring);
+        assert_eq!(Season::from_date(date(12, 1), sydney), Season::Summer);
+    }
+
+    #[test]
+    fn test_parse_season() {
+        assert_eq!("Fall".parse::<Season>(), Ok(Season::Autumn));
+        assert!("monsoon".parse::<Season>().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::render::TerminalRenderer;
+use crate::render::{TerminalRenderer, char_width};
 use crossterm::style::Color;
 use std::io;
 use vte::{Params, Parser, Perform};
@@ -71,9 +71,16 @@ impl ShellOverlay {
     /// Renders the shell overlay onto the terminal renderer
     pub fn render(&self, renderer: &mut TerminalRenderer) -> io::Result<()> {
         for y in 0..self.state.height {
+            let mut covered_by_wide = false;
             for x in 0..self.state.width {
                 let cell = &self.state.cells[y as usize][x as usize];
 
+                // The right half of a wide character belongs to the cell before it
+                if std::mem::take(&mut covered_by_wide) {
+                    continue;
+                }
+                covered_by_wide = char_width(cell.character) == 2;
+
                 // Only render non-space characters or cells with explicit background colors
                 // This allows weather to show through empty spaces
                 if cell.character != ' ' || cell.bg_color != Color::Reset {
@@ -275,17 +282,22 @@ impl OverlayState {
 // Implement the VTE Perform trait to handle ANSI escape sequences
 impl Perform for OverlayState {
     fn print(&mut self, c: char) {
+        let width = char_width(c);
+        if width == 0 {
+            // Combining marks and joiners have no cell of their own
+            return;
+        }
         self.write_char(c);
-        self.advance_cursor();
+        for _ in 0..width {
+            self.advance_cursor();
+        }
     }
 
     fn execute(&mut self, byte: u8) {
         match byte {
-            0x08 => {
+            0x08 if self.cursor_x > 0 => {
                 // Backspace
-                if self.cursor_x > 0 {
-                    self.cursor_x -= 1;
-                }
+                self.cursor_x -= 1;
             }
             0x09 => {
                 // Tab - move to next tab stop (every 8 columns)
@@ -327,28 +339,28 @@ impl Perform for OverlayState {
                 // Cursor Position
                 let row = params.iter().next().and_then(|p| p.first()).copied().unwrap_or(1).saturating_sub(1);
                 let col = params.iter().nth(1).and_then(|p| p.first()).copied().unwrap_or(1).saturating_sub(1);
-                self.cursor_y = (row as u16).min(self.height.saturating_sub(1));
-                self.cursor_x = (col as u16).min(self.width.saturating_sub(1));
+                self.cursor_y = row.min(self.height.saturating_sub(1));
+                self.cursor_x = col.min(self.width.saturating_sub(1));
             }
             'A' => {
                 // Cursor Up
                 let n = params.iter().next().and_then(|p| p.first()).copied().unwrap_or(1);
-                self.cursor_y = self.cursor_y.saturating_sub(n as u16);
+                self.cursor_y = self.cursor_y.saturating_sub(n);
             }
             'B' => {
                 // Cursor Down
                 let n = params.iter().next().and_then(|p| p.first()).copied().unwrap_or(1);
-                self.cursor_y = (self.cursor_y + n as u16).min(self.height - 1);
+                self.cursor_y = (self.cursor_y + n).min(self.height - 1);
             }
             'C' => {
                 // Cursor Forward
                 let n = params.iter().next().and_then(|p| p.first()).copied().unwrap_or(1);
-                self.cursor_x = (self.cursor_x + n as u16).min(self.width - 1);
+                self.cursor_x = (self.cursor_x + n).min(self.width - 1);
             }
             'D' => {
                 // Cursor Back
                 let n = params.iter().next().and_then(|p| p.first()).copied().unwrap_or(1);
-                self.cursor_x = self.cursor_x.saturating_sub(n as u16);
+                self.cursor_x = self.cursor_x.saturating_sub(n);
             }
             'J' => {
                 // Erase in Display
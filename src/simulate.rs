@@ -0,0 +1,220 @@
+use crate::error::ConfigError;
+use crate::weather::{WeatherCondition, WeatherData};
+use chrono::{NaiveTime, Timelike};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// Hours counted as daytime when a clock time is simulated.
+const DAY_HOURS: std::ops::Range<u32> = 6..18;
+
+/// Time of day for simulated weather: `day`, `night`, or a clock time like `21:30`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum TimeOfDay {
+    Day,
+    Night,
+    Clock(NaiveTime),
+}
+
+impl TimeOfDay {
+    pub fn is_day(&self) -> bool {
+        match self {
+            Self::Day => true,
+            Self::Night => false,
+            Self::Clock(time) => DAY_HOURS.contains(&time.hour()),
+        }
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "night" => Ok(Self::Night),
+            other => NaiveTime::parse_from_str(other, "%H:%M")
+                .map(Self::Clock)
+                .map_err(|_| format!("Unknown time of day: '{}'. Use day, night or HH:MM", s)),
+        }
+    }
+}
+
+impl TryFrom<String> for TimeOfDay {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Cloud cover (percent) that looks right for a condition when none is given.
+pub fn typical_cloud_cover(condition: WeatherCondition) -> f64 {
+    match condition {
+        WeatherCondition::Clear => 10.0,
+        WeatherCondition::PartlyCloudy => 40.0,
+        WeatherCondition::Cloudy => 70.0,
+        WeatherCondition::Drizzle
+        | WeatherCondition::RainShowers
+        | WeatherCondition::SnowShowers => 80.0,
+        _ => 95.0,
+    }
+}
+
+/// Parameters for `--simulate`, from the `[simulate]` table and CLI flags. Unset
+/// values fall back to defaults that depend on the simulated condition. Values
+/// are metric like the rest of `WeatherData` (°C, m/s, mm, %, m).
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SimulateParams {
+    pub temperature: Option<f64>,
+    pub wind_speed: Option<f64>,
+    /// Direction the wind blows from, in degrees (0 = north, 90 = east).
+    pub wind_direction: Option<f64>,
+    pub precipitation: Option<f64>,
+    pub cloud_cover: Option<f64>,
+    pub visibility: Option<f64>,
+    /// 0.0 = new, 0.25 = first quarter, 0.5 = full, 0.75 = last quarter.
+    pub moon_phase: Option<f64>,
+    pub time_of_day: Option<TimeOfDay>,
+}
+
+impl SimulateParams {
+    /// Overrides these parameters with any that are set in `other`.
+    pub fn merge(&mut self, other: SimulateParams) {
+        self.temperature = other.temperature.or(self.temperature);
+        self.wind_speed = other.wind_speed.or(self.wind_speed);
+        self.wind_direction = other.wind_direction.or(self.wind_direction);
+        self.precipitation = other.precipitation.or(self.precipitation);
+        self.cloud_cover = other.cloud_cover.or(self.cloud_cover);
+        self.visibility = other.visibility.or(self.visibility);
+        self.moon_phase = other.moon_phase.or(self.moon_phase);
+        self.time_of_day = other.time_of_day.or(self.time_of_day);
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let checks = [
+            ("temperature", self.temperature, -90.0, 60.0),
+            ("wind_speed", self.wind_speed, 0.0, 120.0),
+            ("wind_direction", self.wind_direction, 0.0, 360.0),
+            ("precipitation", self.precipitation, 0.0, 500.0),
+            ("cloud_cover", self.cloud_cover, 0.0, 100.0),
+            ("visibility", self.visibility, 0.0, 100_000.0),
+            ("moon_phase", self.moon_phase, 0.0, 1.0),
+        ];
+
+        for (field, value, min, max) in checks {
+            if let Some(value) = value
+                && !(min..=max).contains(&value)
+            {
+                return Err(ConfigError::InvalidSimulation {
+                    field,
+                    value,
+                    min,
+                    max,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn weather(&self, condition: WeatherCondition) -> WeatherData {
+        let temperature = self.temperature.unwrap_or(20.0);
+
+        WeatherData {
+            condition,
+            temperature,
+            apparent_temperature: temperature - 1.0,
+            humidity: 65.0,
+            precipitation: self.precipitation.unwrap_or(if condition.is_raining() {
+                2.5
+            } else {
+                0.0
+            }),
+            wind_speed: self.wind_speed.unwrap_or(if condition.is_thunderstorm() {
+                45.0
+            } else {
+                10.0
+            }),
+            wind_direction: self.wind_direction.unwrap_or(225.0),
+            cloud_cover: self
+                .cloud_cover
+                .unwrap_or_else(|| typical_cloud_cover(condition)),
+            pressure: 1013.0,
+            visibility: Some(self.visibility.unwrap_or(10000.0)),
+            is_day: self.time_of_day.is_none_or(|time| time.is_day()),
+            moon_phase: Some(self.moon_phase.unwrap_or(0.5)),
+            timestamp: "simulated".to_string(),
+            precipitation_level: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_of_day_parsing() {
+        assert_eq!("Night".parse::<TimeOfDay>(), Ok(TimeOfDay::Night));
+        assert!("07:15".parse::<TimeOfDay>().unwrap().is_day());
+        assert!(!"22:00".parse::<TimeOfDay>().unwrap().is_day());
+        assert!(!"05:59".parse::<TimeOfDay>().unwrap().is_day());
+        assert!("dusk".parse::<TimeOfDay>().is_err());
+    }
+
+    #[test]
+    fn test_defaults_match_condition() {
+        let params = SimulateParams::default();
+
+        let storm = params.weather(WeatherCondition::Thunderstorm);
+        assert_eq!(storm.wind_speed, 45.0);
+        assert_eq!(storm.precipitation, 2.5);
+        assert!(storm.is_day);
+
+        let clear = params.weather(WeatherCondition::Clear);
+        assert_eq!(clear.wind_speed, 10.0);
+        assert_eq!(clear.precipitation, 0.0);
+        assert!(clear.cloud_cover < params.weather(WeatherCondition::Overcast).cloud_cover);
+    }
+
+    #[test]
+    fn test_overrides_and_merge() {
+        let mut params: SimulateParams = toml::from_str(
+            r#"
+temperature = 18.0
+wind_direction = 90.0
+time_of_day = "night"
+"#,
+        )
+        .unwrap();
+        params.merge(SimulateParams {
+            temperature: Some(25.0),
+            moon_phase: Some(0.0),
+            ..SimulateParams::default()
+        });
+
+        let weather = params.weather(WeatherCondition::Clear);
+        assert_eq!(weather.temperature, 25.0);
+        assert_eq!(weather.wind_direction, 90.0);
+        assert_eq!(weather.moon_phase, Some(0.0));
+        assert!(!weather.is_day);
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        let params = SimulateParams {
+            moon_phase: Some(1.5),
+            ..SimulateParams::default()
+        };
+        assert!(matches!(
+            params.validate(),
+            Err(ConfigError::InvalidSimulation {
+                field: "moon_phase",
+                ..
+            })
+        ));
+        assert!(SimulateParams::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,313 @@
+use crate::error::ConfigError;
+use crossterm::style::Color;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Built-in themes, by name. Every theme, built-in or not, is layered over
+/// `default`, so it only has to list the colors it changes.
+const BUILTIN_THEMES: [(&str, &str); 5] = [
+    ("default", include_str!("builtin/default.toml")),
+    ("solarized", include_str!("builtin/solarized.toml")),
+    ("gruvbox", include_str!("builtin/gruvbox.toml")),
+    ("nord", include_str!("builtin/nord.toml")),
+    ("high-contrast", include_str!("builtin/high-contrast.toml")),
+];
+
+/// How many themes deep a chain of `base` themes may go.
+const MAX_BASE_DEPTH: usize = 8;
+
+/// The `theme` setting in config.toml: the name of a built-in theme or of a
+/// file in the themes directory, or a table of colors written inline.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ThemeSetting {
+    Name(String),
+    Inline(toml::Table),
+}
+
+/// Colors for the whole scene, with a palette for day and one for night.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    pub day: Palette,
+    pub night: Palette,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Palette {
+    pub sky: SkyColors,
+    pub ground: GroundColors,
+    pub house: HouseColors,
+    pub precipitation: PrecipitationColors,
+    pub hud: HudColors,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SkyColors {
+    pub sun: Color,
+    pub moon: Color,
+    pub stars: Color,
+    pub stars_dim: Color,
+    pub cloud: Color,
+    pub cloud_shade: Color,
+    pub cloud_dark: Color,
+    pub fog: Color,
+    pub fog_dark: Color,
+    pub birds: Color,
+    /// Background gradient for `--sky-fill`, from the zenith to the horizon.
+    /// The dusk pair is used around sunrise and sunset.
+    pub top: Color,
+    pub horizon: Color,
+    pub dusk_top: Color,
+    pub dusk_horizon: Color,
+    pub overcast_top: Color,
+    pub overcast_horizon: Color,
+    pub storm_top: Color,
+    pub storm_horizon: Color,
+}
+
+/// Summer colors of the lawn and trees; other seasons tint them their own way.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct GroundColors {
+    pub grass: Color,
+    pub grass_blade: Color,
+    pub foliage: Color,
+    pub soil: Color,
+    pub puddle: Color,
+    pub snow: Color,
+    pub frost: Color,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HouseColors {
+    pub wall: Color,
+    pub door: Color,
+    pub roof: Color,
+    pub window: Color,
+    pub window_glow: Color,
+    pub chimney: Color,
+    pub trim: Color,
+    pub smoke: Color,
+    pub smoke_fading: Color,
+    pub smoke_faint: Color,
+    pub fence: Color,
+    pub mailbox: Color,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PrecipitationColors {
+    pub rain: Color,
+    pub rain_highlight: Color,
+    pub rain_far: Color,
+    pub splash: Color,
+    pub ripple: Color,
+    pub snow: Color,
+    pub snow_far: Color,
+    pub hail: Color,
+    pub hail_settled: Color,
+    pub hail_melting: Color,
+    pub ice: Color,
+    pub lightning: Color,
+    pub lightning_flash: Color,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HudColors {
+    pub text: Color,
+    pub history: Color,
+    pub attribution: Color,
+}
+
+impl Theme {
+    pub fn palette(&self, is_day: bool) -> &Palette {
+        if is_day { &self.day } else { &self.night }
+    }
+
+    /// Loads the configured theme, looking for named themes in
+    /// `~/.config/weathr/themes` before the built-in ones.
+    pub fn load(setting: &ThemeSetting) -> Result<Self, ConfigError> {
+        let themes_dir = crate::config::Config::get_themes_dir().ok();
+        Self::load_from(setting, themes_dir.as_deref())
+    }
+
+    pub fn load_from(
+        setting: &ThemeSetting,
+        themes_dir: Option<&Path>,
+    ) -> Result<Self, ConfigError> {
+        let (name, table) = match setting {
+            ThemeSetting::Name(name) => (name.as_str(), named_table(name, themes_dir, 0)?),
+            ThemeSetting::Inline(table) => ("inline", layered(table.clone(), themes_dir, 0)?),
+        };
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|source| ConfigError::InvalidTheme {
+                name: name.to_string(),
+                source,
+            })
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        toml::from_str(BUILTIN_THEMES[0].1).expect("built-in default theme is valid")
+    }
+}
+
+/// The colors of theme `name`, from `<themes_dir>/<name>.toml` if there is one.
+fn named_table(
+    name: &str,
+    themes_dir: Option<&Path>,
+    depth: usize,
+) -> Result<toml::Table, ConfigError> {
+    if depth > MAX_BASE_DEPTH {
+        return Err(ConfigError::ThemeTooDeep(name.to_string()));
+    }
+
+    let user_file = themes_dir
+        .map(|dir| dir.join(format!("{}.toml", name)))
+        .filter(|path| path.exists());
+    let content = match user_file {
+        Some(path) => read_theme_file(&path)?,
+        None => BUILTIN_THEMES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, content)| content.to_string())
+            .ok_or_else(|| ConfigError::UnknownTheme(name.to_string()))?,
+    };
+
+    let table = toml::from_str(&content).map_err(|source| ConfigError::InvalidTheme {
+        name: name.to_string(),
+        source,
+    })?;
+    layered(table, themes_dir, depth + 1)
+}
+
+fn read_theme_file(path: &Path) -> Result<String, ConfigError> {
+    fs::read_to_string(path).map_err(|e| ConfigError::ReadError {
+        path: path.display().to_string(),
+        source: e,
+    })
+}
+
+/// Lays `table` over the theme named by its `base` key, or over the default theme.
+fn layered(
+    mut table: toml::Table,
+    themes_dir: Option<&Path>,
+    depth: usize,
+) -> Result<toml::Table, ConfigError> {
+    let mut merged = match table.remove("base") {
+        Some(toml::Value::String(base)) => named_table(&base, themes_dir, depth)?,
+        _ => toml::from_str(BUILTIN_THEMES[0].1).expect("built-in default theme is valid"),
+    };
+    merge(&mut merged, table);
+    Ok(merged)
+}
+
+fn merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(setting: ThemeSetting) -> Result<Theme, ConfigError> {
+        Theme::load_from(&setting, None)
+    }
+
+    #[test]
+    fn test_builtin_themes_load() {
+        for (name, _) in BUILTIN_THEMES {
+            assert!(
+                load(ThemeSetting::Name(name.to_string())).is_ok(),
+                "{}",
+                name
+            );
+        }
+        let nord = load(ThemeSetting::Name("nord".to_string())).unwrap();
+        assert_ne!(nord, Theme::default());
+        assert_eq!(
+            nord.day.house.roof,
+            Color::Rgb {
+                r: 0x5e,
+                g: 0x81,
+                b: 0xac
+            }
+        );
+        // Colors nord leaves out come from the default theme
+        assert_eq!(nord.night.sky.sun, Theme::default().night.sky.sun);
+
+        let err = load(ThemeSetting::Name("vaporwave".to_string())).unwrap_err();
+        assert_eq!(err.kind(), "UnknownTheme");
+    }
+
+    #[test]
+    fn test_inline_theme_overrides_base() {
+        let table = toml::from_str(
+            r##"
+base = "gruvbox"
+
+[day.house]
+wall = "#ff0000"
+door = "dark_blue"
+"##,
+        )
+        .unwrap();
+        let theme = load(ThemeSetting::Inline(table)).unwrap();
+        let gruvbox = load(ThemeSetting::Name("gruvbox".to_string())).unwrap();
+
+        assert_eq!(theme.day.house.wall, Color::Rgb { r: 255, g: 0, b: 0 });
+        assert_eq!(theme.day.house.door, Color::DarkBlue);
+        assert_eq!(theme.day.house.roof, gruvbox.day.house.roof);
+        assert_eq!(theme.night, gruvbox.night);
+    }
+
+    #[test]
+    fn test_invalid_theme_colors() {
+        let table = toml::from_str("[day.house]\nwall = \"plaid\"\n").unwrap();
+        let err = load(ThemeSetting::Inline(table)).unwrap_err();
+        assert_eq!(err.kind(), "InvalidTheme");
+
+        let table = toml::from_str("[day.house]\nshutters = \"red\"\n").unwrap();
+        assert!(load(ThemeSetting::Inline(table)).is_err());
+    }
+
+    #[test]
+    fn test_user_theme_file() {
+        let dir = std::env::temp_dir().join(format!("weathr_test_themes_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("loop.toml"), "base = \"loop\"\n").unwrap();
+        fs::write(
+            dir.join("sunset.toml"),
+            "base = \"solarized\"\n[night.hud]\ntext = \"magenta\"\n",
+        )
+        .unwrap();
+
+        let theme =
+            Theme::load_from(&ThemeSetting::Name("sunset".to_string()), Some(&dir)).unwrap();
+        assert_eq!(theme.night.hud.text, Color::Magenta);
+
+        let err =
+            Theme::load_from(&ThemeSetting::Name("loop".to_string()), Some(&dir)).unwrap_err();
+        assert_eq!(err.kind(), "ThemeTooDeep");
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -1,5 +1,7 @@
 use crate::cache;
 use crate::error::WeatherError;
+use crate::history::HistoryStore;
+use crate::retry::RetryPolicy;
 use crate::weather::normalizer::WeatherNormalizer;
 use crate::weather::provider::WeatherProvider;
 use crate::weather::types::{WeatherData, WeatherLocation, WeatherUnits};
@@ -12,6 +14,9 @@ pub struct WeatherClient {
     provider: Arc<dyn WeatherProvider>,
     cache: Arc<RwLock<Option<CachedWeather>>>,
     cache_duration: Duration,
+    retry_policy: RetryPolicy,
+    history: Option<HistoryStore>,
+    storage_error: Arc<RwLock<Option<String>>>,
 }
 
 struct CachedWeather {
@@ -25,9 +30,23 @@ impl WeatherClient {
             provider,
             cache: Arc::new(RwLock::new(None)),
             cache_duration,
+            retry_policy: RetryPolicy::default(),
+            history: None,
+            storage_error: Arc::new(RwLock::new(None)),
         }
     }
 
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Appends every reading fetched from the provider (not cache hits) to `history`.
+    pub fn with_history(mut self, history: HistoryStore) -> Self {
+        self.history = Some(history);
+        self
+    }
+
     pub async fn get_current_weather(
         &self,
         location: &WeatherLocation,
@@ -42,9 +61,14 @@ impl WeatherClient {
             }
         }
 
-        if let Some(cached_data) =
-            cache::load_cached_weather(location.latitude, location.longitude).await
-        {
+        let cache_key = cache::WeatherCacheKey::new(
+            location.latitude,
+            location.longitude,
+            self.provider.id(),
+            *units,
+        );
+
+        if let Some(cached_data) = cache::load_cached_weather(&cache_key).await {
             let mut cache = self.cache.write().await;
             *cache = Some(CachedWeather {
                 data: cached_data.clone(),
@@ -53,7 +77,13 @@ impl WeatherClient {
             return Ok(cached_data);
         }
 
-        let response = self.provider.get_current_weather(location, units).await?;
+        let response = self
+            .retry_policy
+            .run(
+                || self.provider.get_current_weather(location, units),
+                WeatherError::is_retryable,
+            )
+            .await?;
 
         let data = WeatherNormalizer::normalize(response);
 
@@ -65,11 +95,28 @@ impl WeatherClient {
             });
         }
 
-        cache::save_weather_cache(&data, location.latitude, location.longitude);
+        // The disk cache and history only speed up restarts, back offline mode and
+        // feed the trend panel, so a failed write is reported separately instead of
+        // failing the fetch
+        let mut storage_error = cache::save_weather_cache(&data, &cache_key)
+            .await
+            .err()
+            .map(|e| e.to_string());
+        if let Some(ref history) = self.history {
+            if let Err(e) = history.append(&data).await {
+                storage_error.get_or_insert_with(|| e.to_string());
+            }
+        }
+        *self.storage_error.write().await = storage_error;
 
         Ok(data)
     }
 
+    /// The error from the most recent cache or history write, if either failed.
+    pub async fn storage_error(&self) -> Option<String> {
+        self.storage_error.read().await.clone()
+    }
+
     #[allow(dead_code)]
     pub async fn invalidate_cache(&self) {
         let mut cache = self.cache.write().await;
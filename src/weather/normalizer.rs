@@ -1,5 +1,5 @@
 use crate::weather::provider::WeatherProviderResponse;
-use crate::weather::types::{WeatherCondition, WeatherData};
+use crate::weather::types::{PrecipitationLevel, WeatherCondition, WeatherData};
 
 pub struct WeatherNormalizer;
 
@@ -21,6 +21,17 @@ impl WeatherNormalizer {
             is_day: response.is_day == 1,
             moon_phase: response.moon_phase,
             timestamp: response.timestamp,
+            precipitation_level: Self::wmo_code_to_level(response.weather_code),
+        }
+    }
+
+    /// Intensity sub-code of precipitation codes; `None` for codes without one.
+    fn wmo_code_to_level(code: i32) -> Option<PrecipitationLevel> {
+        match code {
+            51 | 56 | 61 | 66 | 71 | 80 | 85 | 96 => Some(PrecipitationLevel::Light),
+            53 | 63 | 73 | 81 | 95 => Some(PrecipitationLevel::Moderate),
+            55 | 57 | 65 | 67 | 75 | 82 | 86 | 99 => Some(PrecipitationLevel::Heavy),
+            _ => None,
         }
     }
 
@@ -118,5 +129,20 @@ mod tests {
         assert_eq!(data.temperature, 20.5);
         assert!(data.is_day);
         assert_eq!(data.moon_phase, Some(0.5));
+        assert_eq!(data.precipitation_level, Some(PrecipitationLevel::Light));
+    }
+
+    #[test]
+    fn test_wmo_code_levels() {
+        assert_eq!(
+            WeatherNormalizer::wmo_code_to_level(63),
+            Some(PrecipitationLevel::Moderate)
+        );
+        assert_eq!(
+            WeatherNormalizer::wmo_code_to_level(65),
+            Some(PrecipitationLevel::Heavy)
+        );
+        assert_eq!(WeatherNormalizer::wmo_code_to_level(77), None);
+        assert_eq!(WeatherNormalizer::wmo_code_to_level(0), None);
     }
 }
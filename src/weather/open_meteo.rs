@@ -99,6 +99,10 @@ impl Default for OpenMeteoProvider {
 
 #[async_trait]
 impl WeatherProvider for OpenMeteoProvider {
+    fn id(&self) -> &'static str {
+        "open_meteo"
+    }
+
     async fn get_current_weather(
         &self,
         location: &WeatherLocation,
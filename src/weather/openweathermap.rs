@@ -174,6 +174,10 @@ impl OpenWeatherMapProvider {
 
 #[async_trait]
 impl WeatherProvider for OpenWeatherMapProvider {
+    fn id(&self) -> &'static str {
+        "openweathermap"
+    }
+
     async fn get_current_weather(
         &self,
         location: &WeatherLocation,
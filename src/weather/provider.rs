@@ -22,6 +22,9 @@ pub struct WeatherProviderResponse {
 
 #[async_trait]
 pub trait WeatherProvider: Send + Sync {
+    /// Stable identifier used to key cached data, e.g. "open_meteo".
+    fn id(&self) -> &'static str;
+
     async fn get_current_weather(
         &self,
         location: &WeatherLocation,
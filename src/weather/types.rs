@@ -34,6 +34,27 @@ pub enum SnowIntensity {
     Heavy,
 }
 
+/// Intensity encoded in the WMO weather code (e.g. 61/63/65 for slight, moderate
+/// and heavy rain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrecipitationLevel {
+    Light,
+    Moderate,
+    Heavy,
+}
+
+impl PrecipitationLevel {
+    /// Range of precipitation strength consistent with this level.
+    fn strength_range(&self) -> (f32, f32) {
+        match self {
+            Self::Light => (0.1, 0.45),
+            Self::Moderate => (0.35, 0.75),
+            Self::Heavy => (0.65, 1.0),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FogIntensity {
     Light,
@@ -62,6 +83,18 @@ impl WeatherCondition {
         }
     }
 
+    /// Precipitation strength assumed when neither rate nor sub-code is known.
+    pub fn default_strength(&self) -> f32 {
+        match self {
+            Self::Drizzle | Self::SnowGrains => 0.2,
+            Self::Rain | Self::Snow | Self::FreezingRain => 0.45,
+            Self::RainShowers | Self::SnowShowers => 0.55,
+            Self::Thunderstorm => 0.8,
+            Self::ThunderstormHail => 0.95,
+            _ => 0.0,
+        }
+    }
+
     pub fn fog_intensity(&self) -> FogIntensity {
         match self {
             Self::Fog => FogIntensity::Medium,
@@ -96,6 +129,14 @@ impl WeatherCondition {
     pub fn is_foggy(&self) -> bool {
         matches!(self, Self::Fog)
     }
+
+    pub fn is_hail(&self) -> bool {
+        matches!(self, Self::ThunderstormHail)
+    }
+
+    pub fn is_freezing_rain(&self) -> bool {
+        matches!(self, Self::FreezingRain)
+    }
 }
 
 impl std::str::FromStr for WeatherCondition {
@@ -164,6 +205,58 @@ pub struct WeatherData {
     pub is_day: bool,
     pub moon_phase: Option<f64>,
     pub timestamp: String,
+    #[serde(default)]
+    pub precipitation_level: Option<PrecipitationLevel>,
+}
+
+/// Temperatures (°C) at which rain and snow are drawn as a sleet mix.
+const SLEET_TEMPERATURE: std::ops::RangeInclusive<f64> = -1.0..=2.0;
+
+/// Rain rate in mm/h drawn at full strength.
+const FULL_RAIN_RATE: f64 = 10.0;
+/// Snowfall is reported as water equivalent, so full strength comes much sooner.
+const FULL_SNOW_RATE: f64 = 4.0;
+
+impl WeatherData {
+    /// How hard it is raining or snowing, from 0.0 (nothing) to 1.0 (downpour).
+    /// The reported rate decides, kept within the band of the WMO intensity
+    /// sub-code when there is one; without a rate the sub-code or condition is used.
+    pub fn precipitation_strength(&self) -> f32 {
+        let condition = self.condition;
+        if !condition.is_raining() && !condition.is_snowing() {
+            return 0.0;
+        }
+
+        let level_range = self.precipitation_level.map(|level| level.strength_range());
+        if self.precipitation > 0.0 {
+            let full_rate = if condition.is_snowing() {
+                FULL_SNOW_RATE
+            } else {
+                FULL_RAIN_RATE
+            };
+            // Square root so light rates stay distinguishable from each other
+            let strength = ((self.precipitation / full_rate).sqrt() as f32).clamp(0.05, 1.0);
+            return match level_range {
+                Some((min, max)) => strength.clamp(min, max),
+                None => strength,
+            };
+        }
+
+        match level_range {
+            Some((min, max)) => (min + max) / 2.0,
+            None => condition.default_strength(),
+        }
+    }
+
+    /// Plain rain or snow falling around freezing, which comes down as a mix of both.
+    /// Freezing rain and thunderstorms keep their own look.
+    pub fn is_sleet(&self) -> bool {
+        let condition = self.condition;
+        (condition.is_raining() || condition.is_snowing())
+            && !condition.is_freezing_rain()
+            && !condition.is_thunderstorm()
+            && SLEET_TEMPERATURE.contains(&self.temperature)
+    }
 }
 
 #[derive(Debug, Clone, Copy, serde::Deserialize)]
@@ -213,6 +306,9 @@ pub struct WeatherConditions {
     pub is_thunderstorm: bool,
     pub is_cloudy: bool,
     pub is_foggy: bool,
+    pub is_hail: bool,
+    pub is_freezing_rain: bool,
+    pub is_sleet: bool,
     pub is_day: bool,
 }
 
@@ -224,6 +320,9 @@ impl Default for WeatherConditions {
             is_thunderstorm: false,
             is_cloudy: false,
             is_foggy: false,
+            is_hail: false,
+            is_freezing_rain: false,
+            is_sleet: false,
             is_day: true,
         }
     }
@@ -145,6 +145,10 @@ impl WeatherApiProvider {
 
 #[async_trait]
 impl WeatherProvider for WeatherApiProvider {
+    fn id(&self) -> &'static str {
+        "weatherapi"
+    }
+
     async fn get_current_weather(
         &self,
         location: &WeatherLocation,
@@ -96,10 +96,21 @@ fn test_geolocation_error_user_friendly_message() {
     assert!(msg.contains("invalid") || msg.contains("Invalid"));
     assert!(msg.contains("configured/default"));
 
-    let retries_exhausted = GeolocationError::RetriesExhausted { attempts: 3 };
+    let retries_exhausted = GeolocationError::RetriesExhausted {
+        attempts: 3,
+        last_error: NetworkError::ConnectionRefused {
+            url: "https://ipinfo.io".to_string(),
+        },
+    };
     let msg = retries_exhausted.user_friendly_message();
     assert!(msg.contains("3 attempts"));
+    assert!(msg.contains("connection refused"));
     assert!(msg.contains("configured/default"));
+    let source = std::error::Error::source(&retries_exhausted).unwrap();
+    assert_eq!(
+        source.to_string(),
+        "connection refused for https://ipinfo.io"
+    );
 }
 
 #[test]
@@ -0,0 +1,384 @@
+//! Golden snapshots of the scene and every animation system, rendered headless
+//! with seeded RNGs. After an intended change to how something looks, run
+//! `UPDATE_SNAPSHOTS=1 cargo test --test snapshot_test` and review the diff of
+//! `tests/snapshots/`.
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fs;
+use std::path::PathBuf;
+use weathr::animation::{
+    AnimationController, airplanes::AirplaneSystem, birds::BirdSystem, chimney::ChimneySmoke,
+    clouds::CloudSystem, fireflies::FireflySystem, fog::FogSystem, hail::HailSystem,
+    heat_shimmer::HeatShimmer, icicles::IcicleSystem, leaves::FallingLeaves, moon::MoonSystem,
+    raindrops::RaindropSystem, snow::SnowSystem, stars::StarSystem, sunny::SunnyAnimation,
+    thunderstorm::ThunderstormSystem,
+};
+use weathr::history::HistoryRecord;
+use weathr::render::TerminalRenderer;
+use weathr::scene::WorldScene;
+use weathr::scene::decorations::{DecorationRenderConfig, Decorations};
+use weathr::scene::ground::{Ground, GroundRenderConfig};
+use weathr::scene::house::House;
+use weathr::season::Season;
+use weathr::weather::{
+    FogIntensity, RainIntensity, SnowIntensity, WeatherCondition, WeatherConditions, WeatherData,
+};
+
+const WIDTH: u16 = 80;
+const HEIGHT: u16 = 24;
+const HORIZON_Y: u16 = HEIGHT - WorldScene::GROUND_HEIGHT;
+const SEED: u64 = 42;
+/// Updates to run before rendering, so particle systems fill the screen.
+const WARM_UP_FRAMES: usize = 60;
+
+fn setup() -> (TerminalRenderer, StdRng) {
+    (
+        TerminalRenderer::headless(WIDTH, HEIGHT),
+        StdRng::seed_from_u64(SEED),
+    )
+}
+
+fn assert_snapshot(name: &str, renderer: &TerminalRenderer) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.txt", name));
+    let actual = renderer.to_text();
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot '{}', rerun with UPDATE_SNAPSHOTS=1\n\nactual:\n{}",
+            name, actual
+        )
+    });
+    assert!(
+        actual == expected,
+        "snapshot '{}' changed; rerun with UPDATE_SNAPSHOTS=1 to accept it\n\nexpected:\n{}\nactual:\n{}",
+        name,
+        expected,
+        actual
+    );
+}
+
+fn weather(condition: WeatherCondition, temperature: f64) -> WeatherData {
+    WeatherData {
+        condition,
+        temperature,
+        apparent_temperature: temperature,
+        humidity: 80.0,
+        precipitation: 0.0,
+        wind_speed: 10.0,
+        wind_direction: 270.0,
+        cloud_cover: 90.0,
+        pressure: 1012.0,
+        visibility: None,
+        is_day: true,
+        moon_phase: Some(0.5),
+        timestamp: "2024-01-10T08:00".to_string(),
+        precipitation_level: None,
+    }
+}
+
+#[test]
+fn test_snapshot_world_scene() {
+    let (mut renderer, _) = setup();
+    let scene = WorldScene::new(WIDTH, HEIGHT);
+    scene
+        .render(&mut renderer, &WeatherConditions::default())
+        .unwrap();
+    assert_snapshot("world_scene", &renderer);
+}
+
+#[test]
+fn test_snapshot_world_scene_winter() {
+    let (mut renderer, _) = setup();
+    let mut scene = WorldScene::new(WIDTH, HEIGHT);
+    scene.set_season(Season::Winter);
+    scene.seed_from_history(&[
+        HistoryRecord {
+            recorded_at: 0,
+            data: weather(WeatherCondition::Snow, -4.0),
+        },
+        HistoryRecord {
+            recorded_at: 3600,
+            data: weather(WeatherCondition::Cloudy, -4.0),
+        },
+    ]);
+    scene.update(Some(&weather(WeatherCondition::Cloudy, -4.0)));
+    scene
+        .render(&mut renderer, &WeatherConditions::default())
+        .unwrap();
+    assert_snapshot("world_scene_winter", &renderer);
+}
+
+#[test]
+fn test_snapshot_house() {
+    let (mut renderer, _) = setup();
+    House.render(&mut renderer, 8, 4, true, 0.0, false).unwrap();
+    assert_snapshot("house", &renderer);
+
+    let (mut renderer, _) = setup();
+    renderer.set_unicode(true);
+    House.render(&mut renderer, 8, 4, false, 0.6, true).unwrap();
+    assert_snapshot("house_unicode_snowy", &renderer);
+}
+
+#[test]
+fn test_snapshot_ground() {
+    for (name, season, snow_depth, wetness) in [
+        ("ground_summer", Season::Summer, 0.0, 0.0),
+        ("ground_wet", Season::Spring, 0.0, 1.0),
+        ("ground_snow", Season::Winter, 0.7, 0.0),
+    ] {
+        let (mut renderer, _) = setup();
+        Ground
+            .render(
+                &mut renderer,
+                &GroundRenderConfig {
+                    width: WIDTH,
+                    height: WorldScene::GROUND_HEIGHT,
+                    y_start: HORIZON_Y,
+                    is_day: true,
+                    snow_depth,
+                    wetness,
+                    frost: 0.0,
+                    season,
+                },
+            )
+            .unwrap();
+        assert_snapshot(name, &renderer);
+    }
+}
+
+#[test]
+fn test_snapshot_decorations() {
+    for (name, season) in [
+        ("decorations_summer", Season::Summer),
+        ("decorations_autumn", Season::Autumn),
+        ("decorations_winter", Season::Winter),
+    ] {
+        let (mut renderer, _) = setup();
+        Decorations::new()
+            .render(
+                &mut renderer,
+                &DecorationRenderConfig {
+                    horizon_y: HORIZON_Y,
+                    house_x: (WIDTH - House::WIDTH) / 2,
+                    house_width: House::WIDTH,
+                    width: WIDTH,
+                    is_day: true,
+                    snow_depth: 0.0,
+                    season,
+                },
+            )
+            .unwrap();
+        assert_snapshot(name, &renderer);
+    }
+}
+
+#[test]
+fn test_snapshot_precipitation() {
+    let (mut renderer, mut rng) = setup();
+    let palette = renderer.palette(true);
+    let mut rain = RaindropSystem::new(WIDTH, HEIGHT, RainIntensity::Heavy, &mut rng);
+    rain.set_instant_fade(true);
+    rain.set_active(true);
+    rain.set_ground(HORIZON_Y, 0.0);
+    for _ in 0..WARM_UP_FRAMES {
+        rain.update(WIDTH, HEIGHT, &mut rng);
+    }
+    rain.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("raindrops", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    let mut snow = SnowSystem::new(WIDTH, HEIGHT, SnowIntensity::Medium, &mut rng);
+    snow.set_instant_fade(true);
+    snow.set_active(true);
+    for _ in 0..WARM_UP_FRAMES {
+        snow.update(WIDTH, HEIGHT, &mut rng);
+    }
+    snow.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("snow", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    renderer.set_unicode(true);
+    snow.render(&mut renderer, &palette).unwrap();
+    let mut rain = RaindropSystem::new(WIDTH, HEIGHT, RainIntensity::Light, &mut rng);
+    rain.set_instant_fade(true);
+    rain.set_active(true);
+    for _ in 0..WARM_UP_FRAMES {
+        rain.update(WIDTH, HEIGHT, &mut rng);
+    }
+    rain.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("precipitation_unicode", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    let mut hail = HailSystem::new(WIDTH, HEIGHT);
+    hail.set_instant_fade(true);
+    hail.set_active(true);
+    for _ in 0..WARM_UP_FRAMES {
+        hail.update(WIDTH, HEIGHT, HORIZON_Y, &mut rng);
+    }
+    hail.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("hail", &renderer);
+}
+
+#[test]
+fn test_snapshot_sky() {
+    let (mut renderer, mut rng) = setup();
+    let palette = renderer.palette(true);
+    let mut clouds = CloudSystem::new(WIDTH, HEIGHT, &mut rng);
+    clouds.set_instant_fade(true);
+    clouds.set_cover(70.0);
+    for _ in 0..WARM_UP_FRAMES {
+        clouds.update(WIDTH, HEIGHT, &mut rng);
+    }
+    clouds.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("clouds", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    let mut fog = FogSystem::new(WIDTH, HEIGHT, FogIntensity::Heavy);
+    fog.set_instant_fade(true);
+    fog.set_active(true);
+    for _ in 0..WARM_UP_FRAMES {
+        fog.update(WIDTH, HEIGHT, &mut rng);
+    }
+    fog.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("fog", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    let mut birds = BirdSystem::new(WIDTH, HEIGHT);
+    let mut airplanes = AirplaneSystem::new(WIDTH, HEIGHT);
+    birds.set_spawning(true);
+    for _ in 0..WARM_UP_FRAMES * 5 {
+        birds.update(WIDTH, HEIGHT, &mut rng);
+        airplanes.update(WIDTH, HEIGHT, &mut rng);
+    }
+    birds.render(&mut renderer, &palette).unwrap();
+    airplanes.render(&mut renderer).unwrap();
+    assert_snapshot("birds_and_airplanes", &renderer);
+
+    let (mut renderer, _) = setup();
+    let mut controller = AnimationController::new();
+    let sun = SunnyAnimation::new();
+    controller.next_frame(&sun);
+    controller
+        .render_frame_colored(&mut renderer, &sun, 2, palette.sky.sun)
+        .unwrap();
+    assert_snapshot("sun", &renderer);
+}
+
+#[test]
+fn test_snapshot_night_sky() {
+    let (mut renderer, mut rng) = setup();
+    let palette = renderer.palette(false);
+    let mut stars = StarSystem::new(WIDTH, HEIGHT, &mut rng);
+    let mut moon = MoonSystem::new(WIDTH, HEIGHT);
+    moon.set_phase(0.3);
+    moon.update(WIDTH, HEIGHT);
+    for _ in 0..WARM_UP_FRAMES {
+        stars.update(WIDTH, HEIGHT, &mut rng);
+    }
+    stars.render(&mut renderer, &palette).unwrap();
+    moon.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("stars_and_moon", &renderer);
+
+    renderer.clear().unwrap();
+    renderer.set_unicode(true);
+    stars.render(&mut renderer, &palette).unwrap();
+    moon.render(&mut renderer, &palette).unwrap();
+    assert_snapshot("stars_and_moon_unicode", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    let mut fireflies = FireflySystem::new(WIDTH, HEIGHT);
+    for _ in 0..WARM_UP_FRAMES * 5 {
+        fireflies.update(WIDTH, HEIGHT, HORIZON_Y, &mut rng);
+    }
+    fireflies.render(&mut renderer).unwrap();
+    assert_snapshot("fireflies", &renderer);
+}
+
+#[test]
+fn test_snapshot_thunderstorm() {
+    let (mut renderer, mut rng) = setup();
+    let palette = renderer.palette(true);
+    let mut storm = ThunderstormSystem::new(WIDTH, HEIGHT, &mut rng);
+    // Run until the first bolt is on screen
+    for _ in 0..400 {
+        storm.update(WIDTH, HEIGHT, &mut rng);
+        renderer.clear().unwrap();
+        storm.render(&mut renderer, &palette).unwrap();
+        if !renderer.to_text().trim().is_empty() {
+            break;
+        }
+    }
+    assert_snapshot("thunderstorm", &renderer);
+}
+
+#[test]
+fn test_snapshot_house_effects() {
+    let (mut renderer, mut rng) = setup();
+    let palette = renderer.palette(true);
+    let mut smoke = ChimneySmoke::new();
+    smoke.set_spawning(true);
+    let mut icicles = IcicleSystem::new(House::EAVE_WIDTH);
+    icicles.set_freezing(true);
+    for _ in 0..WARM_UP_FRAMES * 5 {
+        smoke.update(20, 12, &mut rng);
+        icicles.update(&mut rng);
+    }
+    smoke.render(&mut renderer, &palette).unwrap();
+    icicles.render(&mut renderer, &palette, 10, 13).unwrap();
+    assert_snapshot("smoke_and_icicles", &renderer);
+
+    let (mut renderer, mut rng) = setup();
+    let mut shimmer = HeatShimmer::new();
+    shimmer.set_instant_fade(true);
+    shimmer.set_active(true);
+    let mut leaves = FallingLeaves::new(WIDTH, HEIGHT, &mut rng);
+    for _ in 0..WARM_UP_FRAMES {
+        shimmer.update(WIDTH, HORIZON_Y, &mut rng);
+        leaves.update(WIDTH, HEIGHT, &mut rng);
+    }
+    shimmer.render(&mut renderer).unwrap();
+    leaves.render(&mut renderer).unwrap();
+    assert_snapshot("heat_shimmer_and_leaves", &renderer);
+}
+
+#[test]
+fn test_headless_renderer_handles_wide_text() {
+    let mut renderer = TerminalRenderer::headless(10, 2);
+    renderer
+        .render_line_colored(0, 0, "東京 12°C", crossterm::style::Color::White)
+        .unwrap();
+    assert_eq!(renderer.cell(0, 0).unwrap().symbol(), "東");
+    assert_eq!(renderer.cell(1, 0).unwrap().symbol(), "");
+    assert_eq!(renderer.cell(2, 0).unwrap().symbol(), "京");
+
+    // Drawing over half of a wide character blanks the other half
+    renderer
+        .render_char(3, 0, 'x', crossterm::style::Color::White)
+        .unwrap();
+    assert_eq!(renderer.cell(2, 0).unwrap().symbol(), " ");
+    assert_eq!(renderer.to_text(), "東 x 12°C\n\n");
+}
+
+#[test]
+fn test_headless_renderer_writes_ansi() {
+    let mut renderer = TerminalRenderer::headless(6, 3);
+    let red = crossterm::style::Color::Rgb { r: 255, g: 0, b: 0 };
+    let blue = crossterm::style::Color::Rgb { r: 0, g: 0, b: 255 };
+    renderer.render_line_colored(1, 0, "ab", red).unwrap();
+    renderer.fill_background(1, blue).unwrap();
+
+    assert_eq!(
+        renderer.to_ansi(),
+        " \x1b[38;2;255;0;0mab\x1b[0m\n\x1b[48;2;0;0;255m      \x1b[0m\n\n"
+    );
+}
@@ -179,3 +179,38 @@ fn test_weather_normalizer_integration_snowy_conditions() {
     assert!(weather.temperature < 0.0);
     assert!(!weather.is_day);
 }
+
+#[test]
+fn test_weather_normalizer_integration_precipitation_strength() {
+    let rain = |weather_code: i32, precipitation: f64| {
+        WeatherNormalizer::normalize(WeatherProviderResponse {
+            weather_code,
+            temperature: 12.0,
+            apparent_temperature: 11.0,
+            humidity: 90.0,
+            precipitation,
+            wind_speed: 5.0,
+            wind_direction: 200.0,
+            cloud_cover: 100.0,
+            pressure: 1008.0,
+            visibility: Some(5000.0),
+            is_day: 1,
+            moon_phase: None,
+            timestamp: "2024-05-01T09:00".to_string(),
+        })
+        .precipitation_strength()
+    };
+
+    // A downpour and a sprinkle under the same condition look different
+    assert!(rain(63, 12.0) > rain(63, 0.4));
+
+    // Without a rate the sub-code decides
+    assert!(rain(61, 0.0) < rain(63, 0.0));
+    assert!(rain(63, 0.0) < rain(65, 0.0));
+
+    // The rate stays within the band of the reported sub-code
+    assert!(rain(65, 0.2) >= 0.65);
+    assert!(rain(61, 40.0) <= 0.45);
+
+    assert_eq!(rain(0, 3.0), 0.0);
+}